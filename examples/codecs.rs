@@ -0,0 +1,14 @@
+use rsav::codec::Codec;
+
+fn main() {
+    for codec in Codec::iter() {
+        println!(
+            "{}{} {:?} {:<20} {}",
+            if codec.is_decoder() { "D" } else { "." },
+            if codec.is_encoder() { "E" } else { "." },
+            codec.medium(),
+            codec.name(),
+            codec.long_name()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::ffi::CString;
+
+use crate::{codec::CodecParameters, error::Error, ffi, packet::Packet, stream::Stream, Rational};
+
+/// A bitstream filter, or a chain of them such as `h264_mp4toannexb,dump_extra`.
+pub struct BitstreamFilter(*mut ffi::AVBSFContext);
+
+unsafe impl Send for BitstreamFilter {}
+
+impl BitstreamFilter {
+    #[inline]
+    pub(crate) unsafe fn as_ptr(&self) -> *const ffi::AVBSFContext {
+        self.0
+    }
+
+    #[inline]
+    pub(crate) unsafe fn as_mut_ptr(&mut self) -> *mut ffi::AVBSFContext {
+        self.0
+    }
+}
+
+impl BitstreamFilter {
+    /// Allocates a filter from a filter chain description.
+    ///
+    /// The filter must be given its input parameters and initialized with
+    /// [`BitstreamFilter::init`] before use.
+    pub fn new(spec: &str) -> Result<Self, Error> {
+        let spec = CString::new(spec).map_err(|_| Error::InvalidArgument)?;
+        let mut ptr = std::ptr::null_mut();
+
+        unsafe {
+            match ffi::av_bsf_list_parse_str(spec.as_ptr(), &mut ptr) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(BitstreamFilter(ptr)),
+            }
+        }
+    }
+
+    /// Allocates and initializes a filter for the packets of `stream`.
+    pub fn from_stream<D>(spec: &str, stream: &Stream<D>) -> Result<Self, Error> {
+        let mut bsf = Self::new(spec)?;
+        bsf.set_parameters(stream.parameters())?;
+        bsf.set_time_base(stream.time_base());
+        bsf.init()?;
+        Ok(bsf)
+    }
+
+    /// Sets the parameters of the input stream. Must be called before [`BitstreamFilter::init`].
+    pub fn set_parameters<D, P: Into<CodecParameters<D>>>(
+        &mut self,
+        parameters: P,
+    ) -> Result<(), Error> {
+        let parameters = parameters.into();
+
+        unsafe {
+            match ffi::avcodec_parameters_copy((*self.as_mut_ptr()).par_in, parameters.as_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Sets the time base of the input packets. Must be called before [`BitstreamFilter::init`].
+    pub fn set_time_base<R: Into<Rational>>(&mut self, time_base: R) {
+        unsafe {
+            (*self.as_mut_ptr()).time_base_in = time_base.into().into();
+        }
+    }
+
+    pub fn init(&mut self) -> Result<(), Error> {
+        unsafe {
+            match ffi::av_bsf_init(self.as_mut_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    pub fn time_base(&self) -> Rational {
+        unsafe { (*self.as_ptr()).time_base_in.into() }
+    }
+
+    /// Time base of the output packets, available after [`BitstreamFilter::init`].
+    pub fn output_time_base(&self) -> Rational {
+        unsafe { (*self.as_ptr()).time_base_out.into() }
+    }
+
+    /// Parameters of the output stream, available after [`BitstreamFilter::init`].
+    pub fn output_parameters<D>(&self) -> Result<CodecParameters<D>, Error> {
+        let mut parameters = CodecParameters::new();
+
+        unsafe {
+            match ffi::avcodec_parameters_copy(parameters.as_mut_ptr(), (*self.as_ptr()).par_out) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(parameters),
+            }
+        }
+    }
+
+    /// Sends a packet to the filter, which takes its data and leaves it empty on success.
+    ///
+    /// Returns [`Error::Again`] if packets must be received first, in which case `packet`
+    /// is left intact and must be sent again.
+    pub fn send_packet(&mut self, packet: &mut Packet) -> Result<(), Error> {
+        unsafe {
+            match ffi::av_bsf_send_packet(self.as_mut_ptr(), packet.as_mut_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Signals the end of the stream, so that the remaining packets can be drained
+    /// with [`BitstreamFilter::receive_packet`] until it returns [`Error::Eof`].
+    pub fn send_eof(&mut self) -> Result<(), Error> {
+        unsafe {
+            match ffi::av_bsf_send_packet(self.as_mut_ptr(), std::ptr::null_mut()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Receives a filtered packet, in the output time base.
+    ///
+    /// Returns [`Error::Again`] if more input is needed, and [`Error::Eof`] once drained.
+    pub fn receive_packet(&mut self, packet: &mut Packet) -> Result<(), Error> {
+        unsafe {
+            ffi::av_packet_unref(packet.as_mut_ptr());
+
+            match ffi::av_bsf_receive_packet(self.as_mut_ptr(), packet.as_mut_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => {
+                    packet.set_time_base(self.output_time_base());
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Resets the internal state, e.g. after seeking.
+    pub fn flush(&mut self) {
+        unsafe {
+            ffi::av_bsf_flush(self.as_mut_ptr());
+        }
+    }
+}
+
+impl Drop for BitstreamFilter {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::av_bsf_free(&mut self.0);
+        }
+    }
+}
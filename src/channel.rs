@@ -0,0 +1,451 @@
+use std::{
+    ffi::{CStr, CString},
+    str::FromStr,
+};
+
+use crate::{error::Error, ffi};
+
+const AMBISONIC_BASE: i32 = 0x400;
+const AMBISONIC_END: i32 = 0x7ff;
+
+/// A speaker position, or an ambisonic component.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Channel {
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    LowFrequency,
+    BackLeft,
+    BackRight,
+    FrontLeftOfCenter,
+    FrontRightOfCenter,
+    BackCenter,
+    SideLeft,
+    SideRight,
+    TopCenter,
+    TopFrontLeft,
+    TopFrontCenter,
+    TopFrontRight,
+    TopBackLeft,
+    TopBackCenter,
+    TopBackRight,
+    StereoLeft,
+    StereoRight,
+    WideLeft,
+    WideRight,
+    SurroundDirectLeft,
+    SurroundDirectRight,
+    LowFrequency2,
+    TopSideLeft,
+    TopSideRight,
+    BottomFrontCenter,
+    BottomFrontLeft,
+    BottomFrontRight,
+
+    /// Ambisonic component with the given ACN index.
+    Ambisonic(u16),
+    /// A channel that is present but carries no audio.
+    Unused,
+    Unknown,
+}
+
+impl Channel {
+    /// Converts an `AVChannel` value, read as an integer since ambisonic channels
+    /// are not variants of the generated enum.
+    fn from_raw(id: i32) -> Self {
+        match id {
+            0 => Channel::FrontLeft,
+            1 => Channel::FrontRight,
+            2 => Channel::FrontCenter,
+            3 => Channel::LowFrequency,
+            4 => Channel::BackLeft,
+            5 => Channel::BackRight,
+            6 => Channel::FrontLeftOfCenter,
+            7 => Channel::FrontRightOfCenter,
+            8 => Channel::BackCenter,
+            9 => Channel::SideLeft,
+            10 => Channel::SideRight,
+            11 => Channel::TopCenter,
+            12 => Channel::TopFrontLeft,
+            13 => Channel::TopFrontCenter,
+            14 => Channel::TopFrontRight,
+            15 => Channel::TopBackLeft,
+            16 => Channel::TopBackCenter,
+            17 => Channel::TopBackRight,
+            29 => Channel::StereoLeft,
+            30 => Channel::StereoRight,
+            31 => Channel::WideLeft,
+            32 => Channel::WideRight,
+            33 => Channel::SurroundDirectLeft,
+            34 => Channel::SurroundDirectRight,
+            35 => Channel::LowFrequency2,
+            36 => Channel::TopSideLeft,
+            37 => Channel::TopSideRight,
+            38 => Channel::BottomFrontCenter,
+            39 => Channel::BottomFrontLeft,
+            40 => Channel::BottomFrontRight,
+            0x200 => Channel::Unused,
+            AMBISONIC_BASE..=AMBISONIC_END => Channel::Ambisonic((id - AMBISONIC_BASE) as _),
+            _ => Channel::Unknown,
+        }
+    }
+
+    fn into_raw(self) -> Option<i32> {
+        let id = match self {
+            Channel::FrontLeft => 0,
+            Channel::FrontRight => 1,
+            Channel::FrontCenter => 2,
+            Channel::LowFrequency => 3,
+            Channel::BackLeft => 4,
+            Channel::BackRight => 5,
+            Channel::FrontLeftOfCenter => 6,
+            Channel::FrontRightOfCenter => 7,
+            Channel::BackCenter => 8,
+            Channel::SideLeft => 9,
+            Channel::SideRight => 10,
+            Channel::TopCenter => 11,
+            Channel::TopFrontLeft => 12,
+            Channel::TopFrontCenter => 13,
+            Channel::TopFrontRight => 14,
+            Channel::TopBackLeft => 15,
+            Channel::TopBackCenter => 16,
+            Channel::TopBackRight => 17,
+            Channel::StereoLeft => 29,
+            Channel::StereoRight => 30,
+            Channel::WideLeft => 31,
+            Channel::WideRight => 32,
+            Channel::SurroundDirectLeft => 33,
+            Channel::SurroundDirectRight => 34,
+            Channel::LowFrequency2 => 35,
+            Channel::TopSideLeft => 36,
+            Channel::TopSideRight => 37,
+            Channel::BottomFrontCenter => 38,
+            Channel::BottomFrontLeft => 39,
+            Channel::BottomFrontRight => 40,
+            Channel::Ambisonic(_) | Channel::Unused | Channel::Unknown => return None,
+        };
+        Some(id)
+    }
+
+    /// Bit of the channel in the mask of a native layout, if it has one.
+    pub fn mask(self) -> Option<u64> {
+        self.into_raw().map(|id| 1 << id)
+    }
+}
+
+/// How the channels of a layout are described.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ChannelOrder {
+    /// Only the number of channels is known.
+    Unspecified,
+    /// Channels are in the order of their bits in the mask.
+    Native,
+    /// Channels are listed explicitly, in any order.
+    Custom,
+    /// Ambisonic components in ACN order, possibly followed by non-diegetic channels.
+    Ambisonic,
+}
+
+/// Order and number of the channels of an audio stream.
+pub struct ChannelLayout(ffi::AVChannelLayout);
+
+unsafe impl Send for ChannelLayout {}
+unsafe impl Sync for ChannelLayout {}
+
+impl ChannelLayout {
+    #[inline]
+    pub(crate) unsafe fn as_ptr(&self) -> *const ffi::AVChannelLayout {
+        &self.0
+    }
+
+    #[inline]
+    pub(crate) unsafe fn as_mut_ptr(&mut self) -> *mut ffi::AVChannelLayout {
+        &mut self.0
+    }
+
+    /// Copies the layout pointed to by `ptr`.
+    pub(crate) unsafe fn copy_from_ptr(ptr: *const ffi::AVChannelLayout) -> Self {
+        let mut layout = Self::empty();
+        ffi::av_channel_layout_copy(layout.as_mut_ptr(), ptr);
+        layout
+    }
+
+    /// Copies the layout into `dst`, which must be initialized.
+    pub(crate) unsafe fn copy_to_ptr(&self, dst: *mut ffi::AVChannelLayout) -> Result<(), Error> {
+        match ffi::av_channel_layout_copy(dst, self.as_ptr()) {
+            e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ChannelLayout {
+    /// A layout with no channels, of unspecified order.
+    pub fn empty() -> Self {
+        ChannelLayout(unsafe { std::mem::zeroed() })
+    }
+
+    /// A native layout with the channels whose bits are set in `mask`.
+    pub fn from_mask(mask: u64) -> Result<Self, Error> {
+        let mut layout = Self::empty();
+
+        unsafe {
+            match ffi::av_channel_layout_from_mask(layout.as_mut_ptr(), mask) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(layout),
+            }
+        }
+    }
+
+    /// A native layout with the given channels, which must be speaker positions.
+    pub fn from_channels(channels: &[Channel]) -> Result<Self, Error> {
+        let mask = channels.iter().try_fold(0, |mask, channel| {
+            channel
+                .mask()
+                .map(|bit| mask | bit)
+                .ok_or(Error::InvalidArgument)
+        })?;
+
+        Self::from_mask(mask)
+    }
+
+    /// The default layout for `nb_channels` channels, e.g. stereo for 2 and 5.1 for 6.
+    pub fn default_for(nb_channels: u32) -> Self {
+        let mut layout = Self::empty();
+        unsafe {
+            ffi::av_channel_layout_default(layout.as_mut_ptr(), nb_channels as _);
+        }
+        layout
+    }
+
+    pub fn mono() -> Self {
+        Self::standard(&[Channel::FrontCenter])
+    }
+
+    pub fn stereo() -> Self {
+        Self::standard(&[Channel::FrontLeft, Channel::FrontRight])
+    }
+
+    /// 5.1 with back surround channels, named `5.1` by FFmpeg.
+    pub fn surround_5_1() -> Self {
+        Self::standard(&[
+            Channel::FrontLeft,
+            Channel::FrontRight,
+            Channel::FrontCenter,
+            Channel::LowFrequency,
+            Channel::BackLeft,
+            Channel::BackRight,
+        ])
+    }
+
+    /// 5.1 with side surround channels, named `5.1(side)` by FFmpeg.
+    pub fn surround_5_1_side() -> Self {
+        Self::standard(&[
+            Channel::FrontLeft,
+            Channel::FrontRight,
+            Channel::FrontCenter,
+            Channel::LowFrequency,
+            Channel::SideLeft,
+            Channel::SideRight,
+        ])
+    }
+
+    pub fn surround_7_1() -> Self {
+        Self::standard(&[
+            Channel::FrontLeft,
+            Channel::FrontRight,
+            Channel::FrontCenter,
+            Channel::LowFrequency,
+            Channel::BackLeft,
+            Channel::BackRight,
+            Channel::SideLeft,
+            Channel::SideRight,
+        ])
+    }
+
+    fn standard(channels: &[Channel]) -> Self {
+        Self::from_channels(channels).expect("invalid standard channel layout")
+    }
+
+    pub fn order(&self) -> ChannelOrder {
+        match self.0.order {
+            ffi::AVChannelOrder::AV_CHANNEL_ORDER_NATIVE => ChannelOrder::Native,
+            ffi::AVChannelOrder::AV_CHANNEL_ORDER_CUSTOM => ChannelOrder::Custom,
+            ffi::AVChannelOrder::AV_CHANNEL_ORDER_AMBISONIC => ChannelOrder::Ambisonic,
+            _ => ChannelOrder::Unspecified,
+        }
+    }
+
+    #[inline]
+    pub fn nb_channels(&self) -> u32 {
+        self.0.nb_channels as _
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.nb_channels == 0
+    }
+
+    /// The channel mask of native layouts, and of the non-diegetic channels of ambisonic ones.
+    pub fn mask(&self) -> Option<u64> {
+        match self.order() {
+            ChannelOrder::Native | ChannelOrder::Ambisonic => Some(unsafe { self.0.u.mask }),
+            _ => None,
+        }
+    }
+
+    /// Whether the layout is valid, e.g. a native layout whose mask matches its number of channels.
+    pub fn is_valid(&self) -> bool {
+        unsafe { ffi::av_channel_layout_check(self.as_ptr()) == 1 }
+    }
+
+    /// The channels of the layout, in order.
+    pub fn channels(&self) -> Vec<Channel> {
+        let nb_channels = self.nb_channels() as usize;
+
+        match self.order() {
+            ChannelOrder::Unspecified => vec![Channel::Unknown; nb_channels],
+            ChannelOrder::Native => mask_channels(unsafe { self.0.u.mask }).collect(),
+            ChannelOrder::Custom => unsafe {
+                let map = self.0.u.map;
+                (0..nb_channels)
+                    .map(|i| Channel::from_raw(*(map.add(i) as *const i32)))
+                    .collect()
+            },
+            ChannelOrder::Ambisonic => {
+                let mask = unsafe { self.0.u.mask };
+                let nb_ambisonic = nb_channels.saturating_sub(mask.count_ones() as usize);
+                (0..nb_ambisonic)
+                    .map(|i| Channel::Ambisonic(i as _))
+                    .chain(mask_channels(mask))
+                    .collect()
+            }
+        }
+    }
+
+    /// Index of `channel` in the layout.
+    pub fn index_of(&self, channel: Channel) -> Option<usize> {
+        if channel == Channel::Unknown {
+            return None;
+        }
+
+        self.channels().iter().position(|&c| c == channel)
+    }
+
+    #[inline]
+    pub fn contains(&self, channel: Channel) -> bool {
+        self.index_of(channel).is_some()
+    }
+
+    /// Human-readable description, e.g. `stereo`, `5.1(side)` or `3 channels (FL+FR+LFE)`,
+    /// which can be parsed back with [`FromStr`].
+    pub fn describe(&self) -> String {
+        let mut buf = vec![0u8; 64];
+
+        unsafe {
+            loop {
+                let ret = ffi::av_channel_layout_describe(
+                    self.as_ptr(),
+                    buf.as_mut_ptr() as *mut _,
+                    buf.len(),
+                );
+
+                match ret {
+                    e if e < 0 => return String::new(),
+                    size if size as usize > buf.len() => buf.resize(size as usize, 0),
+                    _ => break,
+                }
+            }
+
+            CStr::from_ptr(buf.as_ptr() as *const _)
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+}
+
+fn mask_channels(mask: u64) -> impl Iterator<Item = Channel> {
+    (0..64)
+        .filter(move |bit| mask & (1 << bit) != 0)
+        .map(Channel::from_raw)
+}
+
+impl Default for ChannelLayout {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Clone for ChannelLayout {
+    fn clone(&self) -> Self {
+        unsafe { Self::copy_from_ptr(self.as_ptr()) }
+    }
+}
+
+impl PartialEq for ChannelLayout {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { ffi::av_channel_layout_compare(self.as_ptr(), other.as_ptr()) == 0 }
+    }
+}
+
+impl Eq for ChannelLayout {}
+
+impl FromStr for ChannelLayout {
+    type Err = Error;
+
+    /// Parses a layout name such as `5.1(side)`, a list of channels such as `FL+FR`,
+    /// or a number of channels such as `6c`.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = CString::new(s).map_err(|_| Error::InvalidArgument)?;
+        let mut layout = Self::empty();
+
+        unsafe {
+            match ffi::av_channel_layout_from_string(layout.as_mut_ptr(), s.as_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(layout),
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ChannelLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl std::fmt::Debug for ChannelLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "ChannelLayout({})", self.describe())
+    }
+}
+
+impl Drop for ChannelLayout {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::av_channel_layout_uninit(self.as_mut_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_layout() {
+        let layout: ChannelLayout = "5.1(side)".parse().unwrap();
+        assert_eq!(layout, ChannelLayout::surround_5_1_side());
+        assert_eq!(layout.nb_channels(), 6);
+        assert_eq!(layout.order(), ChannelOrder::Native);
+        assert_eq!(layout.index_of(Channel::SideRight), Some(5));
+        assert_eq!(layout.describe(), "5.1(side)");
+
+        assert_eq!(ChannelLayout::default_for(2), ChannelLayout::stereo());
+        assert_ne!(
+            ChannelLayout::surround_5_1(),
+            ChannelLayout::surround_5_1_side()
+        );
+        assert!("foo".parse::<ChannelLayout>().is_err());
+    }
+}
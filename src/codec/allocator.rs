@@ -0,0 +1,119 @@
+use std::{
+    ffi::c_void,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::ffi;
+
+/// Alignment of the planes and line sizes of the buffers requested from a [`FrameAllocator`].
+pub const FRAME_ALIGN: usize = 64;
+
+/// Extra bytes requested past the last plane, as decoders may read slightly beyond it.
+const PADDING_SIZE: usize = ffi::AV_INPUT_BUFFER_PADDING_SIZE as usize;
+
+/// Memory that a decoder writes a frame into.
+///
+/// The buffer is dropped once no frame references it anymore, which may be on another thread.
+pub type FrameBuffer = Box<dyn AsMut<[u8]> + Send>;
+
+/// Provides the buffers of decoded video frames, see [`Context::set_frame_allocator`].
+///
+/// [`Context::set_frame_allocator`]: super::Context::set_frame_allocator
+pub trait FrameAllocator: Send + Sync + 'static {
+    /// Allocates a buffer of at least `size` bytes, starting at an address aligned to `align` bytes.
+    ///
+    /// Returning `None`, or a buffer that is too small or misaligned, makes the decoder use
+    /// the default FFmpeg allocator for this frame instead.
+    fn allocate(&self, size: usize, align: usize) -> Option<FrameBuffer>;
+}
+
+/// Installed as `AVCodecContext.get_buffer2`, with `opaque` pointing to a `Box<dyn FrameAllocator>`.
+pub(crate) unsafe extern "C" fn get_buffer2(
+    ctx: *mut ffi::AVCodecContext,
+    frame: *mut ffi::AVFrame,
+    flags: libc::c_int,
+) -> libc::c_int {
+    let allocator = &*((*ctx).opaque as *const Box<dyn FrameAllocator>);
+    let direct = !(*ctx).codec.is_null()
+        && (*(*ctx).codec).capabilities & ffi::AV_CODEC_CAP_DR1 as libc::c_int != 0;
+
+    if (*ctx).codec_type == ffi::AVMediaType::AVMEDIA_TYPE_VIDEO
+        && direct
+        && video_buffer(allocator.as_ref(), ctx, frame).is_some()
+    {
+        return 0;
+    }
+
+    ffi::avcodec_default_get_buffer2(ctx, frame, flags)
+}
+
+unsafe fn video_buffer(
+    allocator: &dyn FrameAllocator,
+    ctx: *mut ffi::AVCodecContext,
+    frame: *mut ffi::AVFrame,
+) -> Option<()> {
+    let format: ffi::AVPixelFormat = std::mem::transmute((*frame).format);
+    let mut width = (*frame).width;
+    let mut height = (*frame).height;
+    let mut stride_align = [0; ffi::AV_NUM_DATA_POINTERS as usize];
+    ffi::avcodec_align_dimensions2(ctx, &mut width, &mut height, stride_align.as_mut_ptr());
+
+    let mut linesizes = [0; 4];
+    if ffi::av_image_fill_linesizes(linesizes.as_mut_ptr(), format, width) < 0 {
+        return None;
+    }
+    for (linesize, &align) in linesizes.iter_mut().zip(stride_align.iter()) {
+        let align = FRAME_ALIGN.max(align as usize);
+        *linesize = align_up(*linesize as usize, align) as libc::c_int;
+    }
+
+    let mut sizes = [0usize; 4];
+    let strides = linesizes.map(|linesize| linesize as isize);
+    if ffi::av_image_fill_plane_sizes(sizes.as_mut_ptr(), format, height, strides.as_ptr()) < 0 {
+        return None;
+    }
+
+    let mut offsets = [0usize; 4];
+    let mut size = 0;
+    for (offset, &plane_size) in offsets.iter_mut().zip(sizes.iter()) {
+        *offset = size;
+        size = align_up(size + plane_size, FRAME_ALIGN);
+    }
+    size += PADDING_SIZE;
+
+    let mut buffer = catch_unwind(AssertUnwindSafe(|| allocator.allocate(size, FRAME_ALIGN)))
+        .ok()
+        .flatten()?;
+    let data = (*buffer).as_mut();
+    if data.len() < size || data.as_ptr() as usize % FRAME_ALIGN != 0 {
+        return None;
+    }
+    let base = data.as_mut_ptr();
+
+    let opaque = Box::into_raw(Box::new(buffer));
+    let buf = ffi::av_buffer_create(base, size, Some(free_buffer), opaque as *mut c_void, 0);
+    if buf.is_null() {
+        drop(Box::from_raw(opaque));
+        return None;
+    }
+
+    (*frame).buf[0] = buf;
+    for plane in 0..4 {
+        if sizes[plane] > 0 {
+            (*frame).data[plane] = base.add(offsets[plane]);
+            (*frame).linesize[plane] = linesizes[plane];
+        }
+    }
+    (*frame).extended_data = (*frame).data.as_mut_ptr();
+
+    Some(())
+}
+
+unsafe extern "C" fn free_buffer(opaque: *mut c_void, _data: *mut u8) {
+    drop(Box::from_raw(opaque as *mut FrameBuffer));
+}
+
+#[inline]
+fn align_up(value: usize, align: usize) -> usize {
+    value.div_ceil(align) * align
+}
@@ -1,24 +1,42 @@
-use super::{Codec, CodecId, CodecParameters, Decoder, CodecFlags};
-use crate::{error::Error, ffi, MediaType, Rational};
+use super::{
+    allocator, Codec, CodecFlags, CodecFlags2, CodecId, CodecParameters, Decoder, Discard, Encoder,
+    FrameAllocator, ThreadType,
+};
+use crate::{
+    channel::ChannelLayout,
+    color::{ChromaLocation, ColorPrimaries, ColorRange, ColorSpace, ColorTransferCharacteristic},
+    error::Error,
+    ffi,
+    pixel::PixelFormat,
+    sample::SampleFormat,
+    MediaType, Rational,
+};
 
-pub struct Context(*mut ffi::AVCodecContext);
+pub struct Context {
+    ptr: *mut ffi::AVCodecContext,
+    // Referenced by `AVCodecContext.opaque`, must outlive the codec context.
+    allocator: Option<Box<Box<dyn FrameAllocator>>>,
+}
 
 unsafe impl Send for Context {}
 
 impl Context {
     #[inline]
     pub(crate) unsafe fn wrap(ptr: *mut ffi::AVCodecContext) -> Self {
-        Self(ptr)
+        Self {
+            ptr,
+            allocator: None,
+        }
     }
 
     #[inline]
     pub(crate) unsafe fn as_ptr(&self) -> *const ffi::AVCodecContext {
-        self.0
+        self.ptr
     }
 
     #[inline]
     pub(crate) unsafe fn as_mut_ptr(&mut self) -> *mut ffi::AVCodecContext {
-        self.0
+        self.ptr
     }
 }
 
@@ -27,14 +45,14 @@ impl Context {
         let ptr = unsafe {
             ffi::avcodec_alloc_context3(std::ptr::null())
         };
-        Self(ptr)
+        unsafe { Self::wrap(ptr) }
     }
 
     pub fn from_codec(codec: Codec) -> Self {
         let ptr = unsafe {
             ffi::avcodec_alloc_context3(codec.as_ptr())
         };
-        Self(ptr)
+        unsafe { Self::wrap(ptr) }
     }
 
     pub fn from_parameters<D, P: Into<CodecParameters<D>>>(parameters: P) -> Result<Self, Error> {
@@ -52,6 +70,10 @@ impl Context {
         Decoder(self)
     }
 
+    pub fn as_encoder(self) -> Encoder {
+        Encoder(self)
+    }
+
     pub fn codec(&self) -> Option<Codec> {
         unsafe {
             let ptr = (*self.as_ptr()).codec;
@@ -77,6 +99,16 @@ impl Context {
         }
     }
 
+    pub fn flags2(&self) -> CodecFlags2 {
+        CodecFlags2::from_bits_truncate(unsafe { (*self.as_ptr()).flags2 })
+    }
+
+    pub fn set_flags2(&mut self, flags: CodecFlags2) {
+        unsafe {
+            (*self.as_mut_ptr()).flags2 = flags.bits();
+        }
+    }
+
     pub fn codec_id(&self) -> CodecId {
         CodecId::from(unsafe { (*self.as_ptr()).codec_id })
     }
@@ -102,6 +134,17 @@ impl Context {
         }
     }
 
+    /// Time base of the packets sent to a decoder, used for the decoded frames.
+    pub fn packet_time_base(&self) -> Rational {
+        unsafe { (*self.as_ptr()).pkt_timebase.into() }
+    }
+
+    pub fn set_packet_time_base<R: Into<Rational>>(&mut self, time_base: R) {
+        unsafe {
+            (*self.as_mut_ptr()).pkt_timebase = time_base.into().into();
+        }
+    }
+
     pub fn frame_rate(&self) -> Rational {
         unsafe { (*self.as_ptr()).framerate.into() }
     }
@@ -116,6 +159,377 @@ impl Context {
             }
         }
     }
+
+    pub fn width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).width as _ }
+    }
+
+    pub fn set_width(&mut self, width: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).width = width as _;
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).height as _ }
+    }
+
+    pub fn set_height(&mut self, height: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).height = height as _;
+        }
+    }
+
+    pub fn sample_aspect_ratio(&self) -> Rational {
+        unsafe { (*self.as_ptr()).sample_aspect_ratio.into() }
+    }
+
+    pub fn set_sample_aspect_ratio<R: Into<Rational>>(&mut self, sample_aspect_ratio: R) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_aspect_ratio = sample_aspect_ratio.into().into();
+        }
+    }
+
+    pub fn pixel_format(&self) -> PixelFormat {
+        unsafe { (*self.as_ptr()).pix_fmt.into() }
+    }
+
+    pub fn set_pixel_format(&mut self, format: PixelFormat) {
+        unsafe {
+            (*self.as_mut_ptr()).pix_fmt = format.into();
+        }
+    }
+
+    /// Like the other color properties, set on an encoder before opening it to tag its output.
+    pub fn color_range(&self) -> ColorRange {
+        unsafe { (*self.as_ptr()).color_range.into() }
+    }
+
+    pub fn set_color_range(&mut self, color_range: ColorRange) {
+        unsafe {
+            (*self.as_mut_ptr()).color_range = color_range.into();
+        }
+    }
+
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        unsafe { (*self.as_ptr()).color_primaries.into() }
+    }
+
+    pub fn set_color_primaries(&mut self, color_primaries: ColorPrimaries) {
+        unsafe {
+            (*self.as_mut_ptr()).color_primaries = color_primaries.into();
+        }
+    }
+
+    pub fn color_transfer_characteristic(&self) -> ColorTransferCharacteristic {
+        unsafe { (*self.as_ptr()).color_trc.into() }
+    }
+
+    pub fn set_color_transfer_characteristic(&mut self, transfer: ColorTransferCharacteristic) {
+        unsafe {
+            (*self.as_mut_ptr()).color_trc = transfer.into();
+        }
+    }
+
+    pub fn color_space(&self) -> ColorSpace {
+        unsafe { (*self.as_ptr()).colorspace.into() }
+    }
+
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        unsafe {
+            (*self.as_mut_ptr()).colorspace = color_space.into();
+        }
+    }
+
+    pub fn chroma_location(&self) -> ChromaLocation {
+        unsafe { (*self.as_ptr()).chroma_sample_location.into() }
+    }
+
+    pub fn set_chroma_location(&mut self, chroma_location: ChromaLocation) {
+        unsafe {
+            (*self.as_mut_ptr()).chroma_sample_location = chroma_location.into();
+        }
+    }
+
+    pub fn sample_format(&self) -> SampleFormat {
+        unsafe { (*self.as_ptr()).sample_fmt.into() }
+    }
+
+    pub fn set_sample_format(&mut self, format: SampleFormat) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_fmt = format.into();
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        unsafe { (*self.as_ptr()).sample_rate as _ }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_rate = sample_rate as _;
+        }
+    }
+
+    pub fn channel_layout(&self) -> ChannelLayout {
+        unsafe { ChannelLayout::copy_from_ptr(&(*self.as_ptr()).ch_layout) }
+    }
+
+    pub fn set_channel_layout(&mut self, layout: &ChannelLayout) -> Result<(), Error> {
+        unsafe { layout.copy_to_ptr(&mut (*self.as_mut_ptr()).ch_layout) }
+    }
+
+    pub fn bit_rate(&self) -> i64 {
+        unsafe { (*self.as_ptr()).bit_rate }
+    }
+
+    pub fn set_bit_rate(&mut self, bit_rate: i64) {
+        unsafe {
+            (*self.as_mut_ptr()).bit_rate = bit_rate;
+        }
+    }
+
+    /// Maximum bit rate, used with [`Context::set_rc_buffer_size`] to constrain the rate.
+    pub fn rc_max_rate(&self) -> i64 {
+        unsafe { (*self.as_ptr()).rc_max_rate }
+    }
+
+    pub fn set_rc_max_rate(&mut self, rc_max_rate: i64) {
+        unsafe {
+            (*self.as_mut_ptr()).rc_max_rate = rc_max_rate;
+        }
+    }
+
+    /// Size of the rate control (VBV) buffer, in bits.
+    pub fn rc_buffer_size(&self) -> u32 {
+        unsafe { (*self.as_ptr()).rc_buffer_size as _ }
+    }
+
+    pub fn set_rc_buffer_size(&mut self, rc_buffer_size: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).rc_buffer_size = rc_buffer_size as _;
+        }
+    }
+
+    pub fn qmin(&self) -> i32 {
+        unsafe { (*self.as_ptr()).qmin }
+    }
+
+    pub fn set_qmin(&mut self, qmin: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).qmin = qmin;
+        }
+    }
+
+    pub fn qmax(&self) -> i32 {
+        unsafe { (*self.as_ptr()).qmax }
+    }
+
+    pub fn set_qmax(&mut self, qmax: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).qmax = qmax;
+        }
+    }
+
+    /// Quality of a constant quality encoding, in `FF_QP2LAMBDA` units for video,
+    /// used with [`CodecFlags::QSCALE`].
+    pub fn global_quality(&self) -> i32 {
+        unsafe { (*self.as_ptr()).global_quality }
+    }
+
+    pub fn set_global_quality(&mut self, global_quality: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).global_quality = global_quality;
+        }
+    }
+
+    pub fn gop_size(&self) -> u32 {
+        unsafe { (*self.as_ptr()).gop_size as _ }
+    }
+
+    pub fn set_gop_size(&mut self, gop_size: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).gop_size = gop_size as _;
+        }
+    }
+
+    pub fn max_b_frames(&self) -> u32 {
+        unsafe { (*self.as_ptr()).max_b_frames as _ }
+    }
+
+    pub fn set_max_b_frames(&mut self, max_b_frames: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).max_b_frames = max_b_frames as _;
+        }
+    }
+
+    /// Minimum distance between keyframes.
+    pub fn keyint_min(&self) -> u32 {
+        unsafe { (*self.as_ptr()).keyint_min as _ }
+    }
+
+    pub fn set_keyint_min(&mut self, keyint_min: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).keyint_min = keyint_min as _;
+        }
+    }
+
+    /// Number of reference frames.
+    pub fn refs(&self) -> u32 {
+        unsafe { (*self.as_ptr()).refs as _ }
+    }
+
+    pub fn set_refs(&mut self, refs: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).refs = refs as _;
+        }
+    }
+
+    /// Codec-specific profile, one of [`Codec::profiles`] for encoders.
+    pub fn profile(&self) -> i32 {
+        unsafe { (*self.as_ptr()).profile }
+    }
+
+    pub fn set_profile(&mut self, profile: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).profile = profile;
+        }
+    }
+
+    /// Codec-specific level, `AV_LEVEL_UNKNOWN` letting the encoder choose.
+    pub fn level(&self) -> i32 {
+        unsafe { (*self.as_ptr()).level }
+    }
+
+    pub fn set_level(&mut self, level: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).level = level;
+        }
+    }
+
+    /// Number of threads, `0` letting FFmpeg pick one per CPU core.
+    pub fn threads(&self) -> u32 {
+        unsafe { (*self.as_ptr()).thread_count as _ }
+    }
+
+    pub fn set_threads(&mut self, threads: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).thread_count = threads as _;
+        }
+    }
+
+    pub fn thread_type(&self) -> ThreadType {
+        ThreadType::from_bits_truncate(unsafe { (*self.as_ptr()).thread_type })
+    }
+
+    pub fn set_thread_type(&mut self, thread_type: ThreadType) {
+        unsafe {
+            (*self.as_mut_ptr()).thread_type = thread_type.bits();
+        }
+    }
+
+    /// Decodes at 1 / 2^`lowres` of the full resolution, if the decoder supports it.
+    pub fn lowres(&self) -> u32 {
+        unsafe { (*self.as_ptr()).lowres as _ }
+    }
+
+    pub fn set_lowres(&mut self, lowres: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).lowres = lowres as _;
+        }
+    }
+
+    pub fn skip_frame(&self) -> Discard {
+        unsafe { (*self.as_ptr()).skip_frame.into() }
+    }
+
+    pub fn set_skip_frame(&mut self, discard: Discard) {
+        unsafe {
+            (*self.as_mut_ptr()).skip_frame = discard.into();
+        }
+    }
+
+    pub fn skip_loop_filter(&self) -> Discard {
+        unsafe { (*self.as_ptr()).skip_loop_filter.into() }
+    }
+
+    pub fn set_skip_loop_filter(&mut self, discard: Discard) {
+        unsafe {
+            (*self.as_mut_ptr()).skip_loop_filter = discard.into();
+        }
+    }
+
+    pub fn skip_idct(&self) -> Discard {
+        unsafe { (*self.as_ptr()).skip_idct.into() }
+    }
+
+    pub fn set_skip_idct(&mut self, discard: Discard) {
+        unsafe {
+            (*self.as_mut_ptr()).skip_idct = discard.into();
+        }
+    }
+
+    /// Places codec headers in the extradata instead of every keyframe, as required
+    /// by muxers such as MP4, see [`OutputContainer::needs_global_header`].
+    ///
+    /// [`OutputContainer::needs_global_header`]: crate::container::OutputContainer::needs_global_header
+    pub fn set_global_header(&mut self, global_header: bool) {
+        let mut flags = self.flags();
+        flags.set(CodecFlags::GLOBAL_HEADER, global_header);
+        self.set_flags(flags);
+    }
+
+    /// ASS header of text subtitles, output by decoders and required by text subtitle encoders.
+    pub fn subtitle_header(&self) -> Option<&[u8]> {
+        unsafe {
+            let ctx = self.as_ptr();
+            if (*ctx).subtitle_header.is_null() {
+                None
+            } else {
+                Some(std::slice::from_raw_parts(
+                    (*ctx).subtitle_header,
+                    (*ctx).subtitle_header_size as _,
+                ))
+            }
+        }
+    }
+
+    pub fn set_subtitle_header(&mut self, header: &[u8]) -> Result<(), Error> {
+        let size = libc::c_int::try_from(header.len()).map_err(|_| Error::InvalidArgument)?;
+
+        unsafe {
+            // NUL-terminated, as FFmpeg parses it as a string.
+            let ptr = ffi::av_mallocz(header.len() + 1) as *mut u8;
+            if ptr.is_null() {
+                return Err(Error::OutOfMemory);
+            }
+            std::ptr::copy_nonoverlapping(header.as_ptr(), ptr, header.len());
+
+            let ctx = self.as_mut_ptr();
+            ffi::av_freep(&mut (*ctx).subtitle_header as *mut *mut u8 as *mut _);
+            (*ctx).subtitle_header = ptr;
+            (*ctx).subtitle_header_size = size;
+        }
+
+        Ok(())
+    }
+
+    /// Makes the decoder write video frames into buffers provided by `allocator`,
+    /// e.g. pinned or pooled memory. Must be called before the decoder is opened.
+    ///
+    /// Decoders without direct rendering support, and audio decoders, keep using
+    /// the default allocator.
+    pub fn set_frame_allocator<A: FrameAllocator>(&mut self, allocator: A) {
+        let allocator: Box<Box<dyn FrameAllocator>> = Box::new(Box::new(allocator));
+
+        unsafe {
+            let ctx = self.as_mut_ptr();
+            (*ctx).opaque = &*allocator as *const Box<dyn FrameAllocator> as *mut _;
+            (*ctx).get_buffer2 = Some(allocator::get_buffer2);
+        }
+
+        self.allocator = Some(allocator);
+    }
 }
 
 impl Default for Context {
@@ -127,7 +541,7 @@ impl Default for Context {
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
-            ffi::avcodec_free_context(&mut self.0);
+            ffi::avcodec_free_context(&mut self.ptr);
         }
     }
 }
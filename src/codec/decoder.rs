@@ -1,7 +1,10 @@
 use std::ops::{Deref, DerefMut};
 
 use super::{Codec, CodecParameters, Context};
-use crate::{error::Error, ffi, frame::Frame, packet::Packet};
+use crate::{
+    dictionary::Dictionary, error::Error, ffi, frame::Frame, packet::Packet, stream::Stream,
+    subtitle::Subtitle,
+};
 
 pub struct Decoder(pub(crate) Context);
 
@@ -17,14 +20,34 @@ impl Decoder {
     pub fn from_parameters<D, P: Into<CodecParameters<D>>>(parameters: P) -> Result<Self, Error> {
         Context::from_parameters(parameters).map(Self)
     }
+
+    /// Creates a decoder for the packets of `stream`, with its parameters and time base.
+    pub fn from_stream<D>(stream: &Stream<D>) -> Result<Self, Error> {
+        let mut decoder = Self::from_parameters(stream.parameters())?;
+        decoder.set_packet_time_base(stream.time_base());
+        Ok(decoder)
+    }
 }
 
 impl Decoder {
-    pub fn open(mut self) -> Result<OpenedDecoder, Error> {
+    /// Opens the decoder, looking up a decoder for the codec id if no codec was given.
+    pub fn open(self) -> Result<OpenedDecoder, Error> {
+        self.open_with(&mut Dictionary::new())
+    }
+
+    /// Same as [`Decoder::open`], with codec options such as `threads` or codec-private ones.
+    ///
+    /// On return, `options` only contains the options that were not recognized.
+    pub fn open_with(mut self, options: &mut Dictionary) -> Result<OpenedDecoder, Error> {
         unsafe {
-            let codec = std::ptr::null();
-            let options = std::ptr::null_mut();
-            match ffi::avcodec_open2(self.0.as_mut_ptr(), codec, options) {
+            let codec = match self.0.codec() {
+                Some(_) => std::ptr::null(),
+                None => match Codec::find_decoder_by_id(self.0.codec_id()) {
+                    Some(codec) => codec.as_ptr(),
+                    None => return Err(Error::DecoderNotFound),
+                },
+            };
+            match ffi::avcodec_open2(self.0.as_mut_ptr(), codec, options.as_mut_ptr()) {
                 0 => Ok(OpenedDecoder(self.0)),
                 e => Err(Error::from_ffmpeg_error_code(e)),
             }
@@ -50,7 +73,15 @@ pub struct OpenedDecoder(pub(crate) Context);
 
 impl OpenedDecoder {
     pub fn send_packet(&mut self, packet: Packet) -> Result<(), Error> {
+        self.send_packet_ref(&packet)
+    }
+
+    fn send_packet_ref(&mut self, packet: &Packet) -> Result<(), Error> {
         unsafe {
+            if self.0.packet_time_base().is_none() && !packet.time_base().is_none() {
+                self.0.set_packet_time_base(packet.time_base());
+            }
+
             match ffi::avcodec_send_packet(self.0.as_mut_ptr(), packet.as_ptr()) {
                 e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
                 _ => Ok(()),
@@ -68,15 +99,70 @@ impl OpenedDecoder {
         }
     }
 
+    /// Receives a decoded frame, in the time base of the packets.
+    ///
+    /// Returns [`Error::Again`] if more packets are needed, and [`Error::Eof`] once drained.
     pub fn receive_frame(&mut self, frame: &mut Frame) -> Result<(), Error> {
         unsafe {
             match ffi::avcodec_receive_frame(self.0.as_mut_ptr(), frame.as_mut_ptr()) {
                 e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
-                _ => Ok(()),
+                _ => {
+                    frame.set_time_base(self.0.packet_time_base());
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Decodes a subtitle packet, with timestamps in `AV_TIME_BASE` units.
+    ///
+    /// Returns `None` if the packet did not complete a subtitle.
+    pub fn decode_subtitle(&mut self, packet: &Packet) -> Result<Option<Subtitle>, Error> {
+        let mut subtitle = Subtitle::empty();
+        let mut got_subtitle = 0;
+
+        unsafe {
+            if self.0.packet_time_base().is_none() && !packet.time_base().is_none() {
+                self.0.set_packet_time_base(packet.time_base());
+            }
+
+            match ffi::avcodec_decode_subtitle2(
+                self.0.as_mut_ptr(),
+                subtitle.as_mut_ptr(),
+                &mut got_subtitle,
+                packet.as_ptr(),
+            ) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ if got_subtitle == 0 => Ok(None),
+                _ => Ok(Some(subtitle)),
             }
         }
     }
 
+    /// Sends `packet` to the decoder and iterates over the frames it can output.
+    pub fn decode_packet<'a>(&'a mut self, packet: &'a Packet) -> FrameIter<'a> {
+        FrameIter::new(self, Pending::Packet(packet))
+    }
+
+    /// Signals the end of the stream and iterates over the remaining frames.
+    pub fn decode_eof(&mut self) -> FrameIter {
+        FrameIter::new(self, Pending::Eof)
+    }
+
+    /// Decodes all `packets`, then drains the decoder.
+    pub fn decode<I>(&mut self, packets: I) -> DecodeIter<I::IntoIter>
+    where
+        I: IntoIterator<Item = Packet>,
+    {
+        DecodeIter {
+            decoder: self,
+            packets: packets.into_iter(),
+            packet: None,
+            draining: false,
+            done: false,
+        }
+    }
+
     pub fn flush(&mut self) {
         unsafe {
             ffi::avcodec_flush_buffers(self.0.as_mut_ptr());
@@ -91,3 +177,123 @@ impl Drop for OpenedDecoder {
         }
     }
 }
+
+enum Pending<'a> {
+    Packet(&'a Packet),
+    Eof,
+    None,
+}
+
+/// Iterator over the frames output by a decoder after sending it a packet or the end of stream.
+///
+/// If the decoder is full, the frames it already holds are output before sending the packet again.
+pub struct FrameIter<'a> {
+    decoder: &'a mut OpenedDecoder,
+    pending: Pending<'a>,
+}
+
+impl<'a> FrameIter<'a> {
+    fn new(decoder: &'a mut OpenedDecoder, pending: Pending<'a>) -> Self {
+        FrameIter { decoder, pending }
+    }
+
+    fn send_pending(&mut self) -> Result<(), Error> {
+        let ret = match self.pending {
+            Pending::Packet(packet) => self.decoder.send_packet_ref(packet),
+            Pending::Eof => self.decoder.send_eof(),
+            Pending::None => return Ok(()),
+        };
+
+        match ret {
+            Err(Error::Again) => Ok(()),
+            Err(Error::Eof) if matches!(self.pending, Pending::Eof) => {
+                self.pending = Pending::None;
+                Ok(())
+            }
+            ret => {
+                self.pending = Pending::None;
+                ret
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for FrameIter<'a> {
+    type Item = Result<Frame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.send_pending() {
+            return Some(Err(e));
+        }
+
+        let mut frame = Frame::empty();
+
+        match self.decoder.receive_frame(&mut frame) {
+            Ok(()) => Some(Ok(frame)),
+            Err(Error::Again) | Err(Error::Eof) => match self.pending {
+                // The decoder refused the packet but has no frame to output.
+                Pending::Packet(_) | Pending::Eof => {
+                    self.pending = Pending::None;
+                    Some(Err(Error::Again))
+                }
+                Pending::None => None,
+            },
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Iterator over the frames decoded from a sequence of packets, see [`OpenedDecoder::decode`].
+pub struct DecodeIter<'a, I> {
+    decoder: &'a mut OpenedDecoder,
+    packets: I,
+    packet: Option<Packet>,
+    draining: bool,
+    done: bool,
+}
+
+impl<'a, I: Iterator<Item = Packet>> Iterator for DecodeIter<'a, I> {
+    type Item = Result<Frame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            if let Some(packet) = self.packet.as_ref() {
+                match self.decoder.send_packet_ref(packet) {
+                    Ok(()) => self.packet = None,
+                    Err(Error::Again) => {}
+                    Err(e) => {
+                        self.packet = None;
+                        return Some(Err(e));
+                    }
+                }
+            }
+
+            let mut frame = Frame::empty();
+
+            match self.decoder.receive_frame(&mut frame) {
+                Ok(()) => return Some(Ok(frame)),
+                Err(Error::Again) if self.packet.is_some() => {
+                    // The decoder refused the packet but has no frame to output.
+                    self.packet = None;
+                    return Some(Err(Error::Again));
+                }
+                Err(Error::Again) if !self.draining => match self.packets.next() {
+                    Some(packet) => self.packet = Some(packet),
+                    None => {
+                        self.draining = true;
+                        if let Err(e) = self.decoder.send_eof() {
+                            self.done = true;
+                            return Some(Err(e));
+                        }
+                    }
+                },
+                Err(Error::Again) | Err(Error::Eof) => self.done = true,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::ffi::CStr;
+
+use super::CodecId;
+use crate::{ffi, MediaType};
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct CodecProperties: libc::c_int {
+        const INTRA_ONLY = ffi::AV_CODEC_PROP_INTRA_ONLY;
+        const LOSSY      = ffi::AV_CODEC_PROP_LOSSY;
+        const LOSSLESS   = ffi::AV_CODEC_PROP_LOSSLESS;
+        const REORDER    = ffi::AV_CODEC_PROP_REORDER;
+        const FIELDS     = ffi::AV_CODEC_PROP_FIELDS;
+        const BITMAP_SUB = ffi::AV_CODEC_PROP_BITMAP_SUB;
+        const TEXT_SUB   = ffi::AV_CODEC_PROP_TEXT_SUB;
+    }
+}
+
+/// Static properties of a codec id, shared by all its encoders and decoders.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CodecDescriptor(*const ffi::AVCodecDescriptor);
+
+unsafe impl Send for CodecDescriptor {}
+unsafe impl Sync for CodecDescriptor {}
+
+impl CodecDescriptor {
+    #[inline]
+    pub(crate) unsafe fn wrap(ptr: *const ffi::AVCodecDescriptor) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(CodecDescriptor(ptr))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn as_ptr(&self) -> *const ffi::AVCodecDescriptor {
+        self.0
+    }
+}
+
+impl CodecDescriptor {
+    #[inline]
+    pub fn id(&self) -> CodecId {
+        unsafe { (*self.as_ptr()).id.into() }
+    }
+
+    #[inline]
+    pub fn medium(&self) -> MediaType {
+        unsafe { (*self.as_ptr()).type_.into() }
+    }
+
+    /// Short name of the codec, e.g. `h264`.
+    pub fn name(&self) -> &'static str {
+        unsafe {
+            CStr::from_ptr((*self.as_ptr()).name)
+                .to_str()
+                .unwrap_or_default()
+        }
+    }
+
+    pub fn long_name(&self) -> &'static str {
+        unsafe {
+            let ptr = (*self.as_ptr()).long_name;
+            if ptr.is_null() {
+                ""
+            } else {
+                CStr::from_ptr(ptr).to_str().unwrap_or_default()
+            }
+        }
+    }
+
+    #[inline]
+    pub fn props(&self) -> CodecProperties {
+        CodecProperties::from_bits_truncate(unsafe { (*self.as_ptr()).props })
+    }
+
+    /// MIME types of the codec, mostly set for image and subtitle codecs.
+    pub fn mime_types(&self) -> Vec<&'static str> {
+        let mut mime_types = Vec::new();
+
+        unsafe {
+            let mut ptr = (*self.as_ptr()).mime_types;
+            while !ptr.is_null() && !(*ptr).is_null() {
+                mime_types.push(CStr::from_ptr(*ptr).to_str().unwrap_or_default());
+                ptr = ptr.add(1);
+            }
+        }
+
+        mime_types
+    }
+}
+
+impl std::fmt::Debug for CodecDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CodecDescriptor")
+            .field("id", &self.id())
+            .field("medium", &self.medium())
+            .field("name", &self.name())
+            .field("props", &self.props())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_props() {
+        let h264 = CodecId::H264.descriptor().unwrap();
+        assert!(h264
+            .props()
+            .contains(CodecProperties::LOSSY | CodecProperties::REORDER));
+
+        let subrip = CodecId::SUBRIP.descriptor().unwrap();
+        assert!(subrip.props().contains(CodecProperties::TEXT_SUB));
+        assert!(!subrip.props().contains(CodecProperties::BITMAP_SUB));
+    }
+}
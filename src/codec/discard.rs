@@ -0,0 +1,41 @@
+use crate::ffi;
+
+/// Which frames a decoder may skip, from none to all of them.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug)]
+pub enum Discard {
+    None,
+    Default,
+    NonRef,
+    Bidir,
+    NonIntra,
+    NonKey,
+    All,
+}
+
+impl From<ffi::AVDiscard> for Discard {
+    fn from(value: ffi::AVDiscard) -> Self {
+        match value {
+            ffi::AVDiscard::AVDISCARD_NONE => Discard::None,
+            ffi::AVDiscard::AVDISCARD_DEFAULT => Discard::Default,
+            ffi::AVDiscard::AVDISCARD_NONREF => Discard::NonRef,
+            ffi::AVDiscard::AVDISCARD_BIDIR => Discard::Bidir,
+            ffi::AVDiscard::AVDISCARD_NONINTRA => Discard::NonIntra,
+            ffi::AVDiscard::AVDISCARD_NONKEY => Discard::NonKey,
+            ffi::AVDiscard::AVDISCARD_ALL => Discard::All,
+        }
+    }
+}
+
+impl From<Discard> for ffi::AVDiscard {
+    fn from(value: Discard) -> ffi::AVDiscard {
+        match value {
+            Discard::None => ffi::AVDiscard::AVDISCARD_NONE,
+            Discard::Default => ffi::AVDiscard::AVDISCARD_DEFAULT,
+            Discard::NonRef => ffi::AVDiscard::AVDISCARD_NONREF,
+            Discard::Bidir => ffi::AVDiscard::AVDISCARD_BIDIR,
+            Discard::NonIntra => ffi::AVDiscard::AVDISCARD_NONINTRA,
+            Discard::NonKey => ffi::AVDiscard::AVDISCARD_NONKEY,
+            Discard::All => ffi::AVDiscard::AVDISCARD_ALL,
+        }
+    }
+}
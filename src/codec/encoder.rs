@@ -0,0 +1,186 @@
+use std::ops::{Deref, DerefMut};
+
+use super::{Codec, CodecId, CodecParameters, Context};
+use crate::{
+    dictionary::Dictionary, error::Error, ffi, frame::Frame, packet::Packet, subtitle::Subtitle,
+    Rational,
+};
+
+/// Size of the buffer subtitles are encoded into, as used by the `ffmpeg` tool.
+const MAX_SUBTITLE_SIZE: usize = 1024 * 1024;
+
+pub struct Encoder(pub(crate) Context);
+
+impl Encoder {
+    pub fn new(codec: Codec) -> Self {
+        Self(Context::from_codec(codec))
+    }
+
+    pub fn from_id(id: CodecId) -> Result<Self, Error> {
+        Codec::find_encoder_by_id(id)
+            .map(Self::new)
+            .ok_or(Error::EncoderNotFound)
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        Codec::find_encoder_by_name(name)
+            .map(Self::new)
+            .ok_or(Error::EncoderNotFound)
+    }
+}
+
+impl Encoder {
+    pub fn open(self) -> Result<OpenedEncoder, Error> {
+        self.open_with(&mut Dictionary::new())
+    }
+
+    /// Opens the encoder with codec options, e.g. `preset` or `crf` for libx264.
+    ///
+    /// On return, `options` only contains the options that were not recognized.
+    pub fn open_with(mut self, options: &mut Dictionary) -> Result<OpenedEncoder, Error> {
+        unsafe {
+            let codec = std::ptr::null();
+            match ffi::avcodec_open2(self.0.as_mut_ptr(), codec, options.as_mut_ptr()) {
+                0 => Ok(OpenedEncoder(self.0)),
+                e => Err(Error::from_ffmpeg_error_code(e)),
+            }
+        }
+    }
+}
+
+impl Deref for Encoder {
+    type Target = Context;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Encoder {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+pub struct OpenedEncoder(pub(crate) Context);
+
+impl OpenedEncoder {
+    pub fn send_frame(&mut self, frame: &Frame) -> Result<(), Error> {
+        unsafe {
+            match ffi::avcodec_send_frame(self.0.as_mut_ptr(), frame.as_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Sends a NULL frame to the encoder to signal the end of the stream and enter draining mode.
+    pub fn send_eof(&mut self) -> Result<(), Error> {
+        unsafe {
+            match ffi::avcodec_send_frame(self.0.as_mut_ptr(), std::ptr::null()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Receives an encoded packet, in the time base of the encoder.
+    ///
+    /// Returns [`Error::Again`] if more frames are needed, and [`Error::Eof`] once drained.
+    pub fn receive_packet(&mut self, packet: &mut Packet) -> Result<(), Error> {
+        unsafe {
+            match ffi::avcodec_receive_packet(self.0.as_mut_ptr(), packet.as_mut_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => {
+                    packet.set_time_base(self.0.time_base());
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Encodes a subtitle into a packet, in the time base of the encoder.
+    ///
+    /// Encoders require the display to start at the pts, so the start display time
+    /// is folded into the pts of the packet, like the `ffmpeg` tool does.
+    pub fn encode_subtitle(&mut self, subtitle: &Subtitle) -> Result<Packet, Error> {
+        let mut buf = vec![0u8; MAX_SUBTITLE_SIZE];
+
+        // Shallow copy, sharing the rects of `subtitle`.
+        let mut local = unsafe { *subtitle.as_ptr() };
+        local.pts = subtitle.start().unwrap_or(ffi::AV_NOPTS_VALUE);
+        local.end_display_time = subtitle
+            .end_display_time()
+            .saturating_sub(subtitle.start_display_time());
+        local.start_display_time = 0;
+
+        let size = unsafe {
+            ffi::avcodec_encode_subtitle(
+                self.0.as_mut_ptr(),
+                buf.as_mut_ptr(),
+                buf.len() as _,
+                &local,
+            )
+        };
+        if size < 0 {
+            return Err(Error::from_ffmpeg_error_code(size));
+        }
+
+        let mut packet = Packet::try_new(size as _)?;
+        if let Some(data) = packet.data_mut() {
+            data.copy_from_slice(&buf[..size as usize]);
+        }
+
+        let time_base = self.0.time_base();
+        let pts = subtitle.start().map(|start| unsafe {
+            ffi::av_rescale_q(start, ffi::AV_TIME_BASE_Q, time_base.into())
+        });
+
+        packet.set_pts(pts);
+        packet.set_dts(pts);
+        packet.set_duration(unsafe {
+            ffi::av_rescale_q(
+                local.end_display_time as _,
+                Rational::new(1, 1000).into(),
+                time_base.into(),
+            )
+        });
+        packet.set_time_base(time_base);
+
+        Ok(packet)
+    }
+
+    /// Returns the stream parameters of the encoder, to be set on an output stream.
+    pub fn parameters<D>(&self) -> Result<CodecParameters<D>, Error> {
+        let mut parameters = CodecParameters::new();
+
+        unsafe {
+            match ffi::avcodec_parameters_from_context(parameters.as_mut_ptr(), self.0.as_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(parameters),
+            }
+        }
+    }
+
+    pub fn flush(&mut self) {
+        unsafe {
+            ffi::avcodec_flush_buffers(self.0.as_mut_ptr());
+        }
+    }
+}
+
+impl Deref for OpenedEncoder {
+    type Target = Context;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for OpenedEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::avcodec_close(self.0.as_mut_ptr());
+        }
+    }
+}
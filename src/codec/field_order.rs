@@ -0,0 +1,42 @@
+use crate::ffi;
+
+/// Order of the fields of interlaced video.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum FieldOrder {
+    Unknown,
+    Progressive,
+    /// Top field coded and displayed first.
+    TT,
+    /// Bottom field coded and displayed first.
+    BB,
+    /// Top field coded first, bottom displayed first.
+    TB,
+    /// Bottom field coded first, top displayed first.
+    BT,
+}
+
+impl From<ffi::AVFieldOrder> for FieldOrder {
+    fn from(value: ffi::AVFieldOrder) -> Self {
+        match value {
+            ffi::AVFieldOrder::AV_FIELD_UNKNOWN => FieldOrder::Unknown,
+            ffi::AVFieldOrder::AV_FIELD_PROGRESSIVE => FieldOrder::Progressive,
+            ffi::AVFieldOrder::AV_FIELD_TT => FieldOrder::TT,
+            ffi::AVFieldOrder::AV_FIELD_BB => FieldOrder::BB,
+            ffi::AVFieldOrder::AV_FIELD_TB => FieldOrder::TB,
+            ffi::AVFieldOrder::AV_FIELD_BT => FieldOrder::BT,
+        }
+    }
+}
+
+impl From<FieldOrder> for ffi::AVFieldOrder {
+    fn from(value: FieldOrder) -> ffi::AVFieldOrder {
+        match value {
+            FieldOrder::Unknown => ffi::AVFieldOrder::AV_FIELD_UNKNOWN,
+            FieldOrder::Progressive => ffi::AVFieldOrder::AV_FIELD_PROGRESSIVE,
+            FieldOrder::TT => ffi::AVFieldOrder::AV_FIELD_TT,
+            FieldOrder::BB => ffi::AVFieldOrder::AV_FIELD_BB,
+            FieldOrder::TB => ffi::AVFieldOrder::AV_FIELD_TB,
+            FieldOrder::BT => ffi::AVFieldOrder::AV_FIELD_BT,
+        }
+    }
+}
@@ -8,9 +8,37 @@ bitflags! {
         const _4MV            = ffi::AV_CODEC_FLAG_4MV;
         const OUTPUT_CORRUPT  = ffi::AV_CODEC_FLAG_OUTPUT_CORRUPT;
         const QPEL            = ffi::AV_CODEC_FLAG_QPEL;
+        const RECON_FRAME     = ffi::AV_CODEC_FLAG_RECON_FRAME;
+        const COPY_OPAQUE     = ffi::AV_CODEC_FLAG_COPY_OPAQUE;
+        const FRAME_DURATION  = ffi::AV_CODEC_FLAG_FRAME_DURATION;
         const PASS1           = ffi::AV_CODEC_FLAG_PASS1;
         const PASS2           = ffi::AV_CODEC_FLAG_PASS2;
+        const LOOP_FILTER     = ffi::AV_CODEC_FLAG_LOOP_FILTER;
         const GRAY            = ffi::AV_CODEC_FLAG_GRAY;
         const PSNR            = ffi::AV_CODEC_FLAG_PSNR;
+        const INTERLACED_DCT  = ffi::AV_CODEC_FLAG_INTERLACED_DCT;
+        const LOW_DELAY       = ffi::AV_CODEC_FLAG_LOW_DELAY;
+        const GLOBAL_HEADER   = ffi::AV_CODEC_FLAG_GLOBAL_HEADER;
+        const BITEXACT        = ffi::AV_CODEC_FLAG_BITEXACT;
+        const AC_PRED         = ffi::AV_CODEC_FLAG_AC_PRED;
+        const INTERLACED_ME   = ffi::AV_CODEC_FLAG_INTERLACED_ME;
+        const CLOSED_GOP      = ffi::AV_CODEC_FLAG_CLOSED_GOP;
+    }
+}
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct CodecFlags2: libc::c_int {
+        const FAST            = ffi::AV_CODEC_FLAG2_FAST;
+        const NO_OUTPUT       = ffi::AV_CODEC_FLAG2_NO_OUTPUT;
+        const LOCAL_HEADER    = ffi::AV_CODEC_FLAG2_LOCAL_HEADER;
+        const CHUNKS          = ffi::AV_CODEC_FLAG2_CHUNKS;
+        const IGNORE_CROP     = ffi::AV_CODEC_FLAG2_IGNORE_CROP;
+        const SHOW_ALL        = ffi::AV_CODEC_FLAG2_SHOW_ALL;
+        const EXPORT_MVS      = ffi::AV_CODEC_FLAG2_EXPORT_MVS;
+        const SKIP_MANUAL     = ffi::AV_CODEC_FLAG2_SKIP_MANUAL;
+        const RO_FLUSH_NOOP   = ffi::AV_CODEC_FLAG2_RO_FLUSH_NOOP;
+        // Above `i32::MAX`, so generated as an unsigned constant.
+        const ICC_PROFILES    = ffi::AV_CODEC_FLAG2_ICC_PROFILES as libc::c_int;
     }
 }
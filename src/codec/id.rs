@@ -1,4 +1,7 @@
-use crate::{ffi, MediaType};
+use std::{ffi::CString, str::FromStr};
+
+use super::CodecDescriptor;
+use crate::{error::Error, ffi, MediaType};
 
 #[allow(non_camel_case_types)]
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
@@ -563,6 +566,41 @@ impl CodecId {
             MediaType::from(media_type)
         }
     }
+
+    pub fn descriptor(&self) -> Option<CodecDescriptor> {
+        unsafe { CodecDescriptor::wrap(ffi::avcodec_descriptor_get((*self).into())) }
+    }
+
+    pub fn long_name(&self) -> &'static str {
+        self.descriptor().map_or("", |desc| desc.long_name())
+    }
+
+    pub fn mime_types(&self) -> Vec<&'static str> {
+        self.descriptor()
+            .map(|desc| desc.mime_types())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for CodecId {
+    type Err = Error;
+
+    /// Parses the short name of a codec, e.g. `h264` or `subrip`.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let name = CString::new(s).map_err(|_| Error::InvalidArgument)?;
+
+        unsafe {
+            CodecDescriptor::wrap(ffi::avcodec_descriptor_get_by_name(name.as_ptr()))
+                .map(|desc| desc.id())
+                .ok_or_else(|| Error::UnknownCodec(s.to_owned()))
+        }
+    }
+}
+
+impl std::fmt::Display for CodecId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 impl From<ffi::AVCodecID> for CodecId {
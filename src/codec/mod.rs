@@ -1,16 +1,36 @@
-use crate::{ffi, MediaType};
+use std::ffi::CStr;
 
+use crate::{
+    channel::ChannelLayout, ffi, pixel::PixelFormat, sample::SampleFormat, MediaType, Rational,
+};
+
+mod allocator;
+mod capabilities;
 mod context;
 mod decoder;
+mod descriptor;
+mod discard;
+mod encoder;
+mod field_order;
 mod flag;
 mod id;
 mod parameters;
+mod parser;
+mod threading;
 
+pub use allocator::{FrameAllocator, FrameBuffer, FRAME_ALIGN};
+pub use capabilities::Capabilities;
 pub use context::Context;
-pub use decoder::Decoder;
-pub use flag::CodecFlags;
+pub use decoder::{Decoder, OpenedDecoder};
+pub use descriptor::{CodecDescriptor, CodecProperties};
+pub use discard::Discard;
+pub use encoder::{Encoder, OpenedEncoder};
+pub use field_order::FieldOrder;
+pub use flag::{CodecFlags, CodecFlags2};
 pub use id::CodecId;
-pub use parameters::CodecParameters;
+pub use parameters::{CodecParameters, CodecParametersBuilder, CodecParametersMut};
+pub use parser::Parser;
+pub use threading::ThreadType;
 
 pub struct Codec(*const ffi::AVCodec);
 
@@ -129,3 +149,196 @@ impl Codec {
         self.medium() == MediaType::Audio
     }
 }
+
+impl Codec {
+    /// Iterates over all codecs of the linked FFmpeg build.
+    pub fn iter() -> CodecIter {
+        CodecIter {
+            opaque: std::ptr::null_mut(),
+            kind: None,
+            medium: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CodecKind {
+    Encoder,
+    Decoder,
+}
+
+/// Iterator over the codecs of the linked FFmpeg build, see [`Codec::iter`].
+pub struct CodecIter {
+    opaque: *mut libc::c_void,
+    kind: Option<CodecKind>,
+    medium: Option<MediaType>,
+}
+
+unsafe impl Send for CodecIter {}
+
+impl CodecIter {
+    /// Only yields encoders.
+    pub fn encoders(mut self) -> Self {
+        self.kind = Some(CodecKind::Encoder);
+        self
+    }
+
+    /// Only yields decoders.
+    pub fn decoders(mut self) -> Self {
+        self.kind = Some(CodecKind::Decoder);
+        self
+    }
+
+    /// Only yields codecs of the given media type.
+    pub fn medium(mut self, medium: MediaType) -> Self {
+        self.medium = Some(medium);
+        self
+    }
+}
+
+impl Iterator for CodecIter {
+    type Item = Codec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let codec = unsafe {
+                let ptr = ffi::av_codec_iterate(&mut self.opaque);
+                if ptr.is_null() {
+                    return None;
+                }
+                Codec::wrap(ptr)
+            };
+
+            let kind_matches = match self.kind {
+                Some(CodecKind::Encoder) => codec.is_encoder(),
+                Some(CodecKind::Decoder) => codec.is_decoder(),
+                None => true,
+            };
+
+            if kind_matches && self.medium.is_none_or(|medium| codec.medium() == medium) {
+                return Some(codec);
+            }
+        }
+    }
+}
+
+impl Codec {
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::from_bits_truncate(unsafe { (*self.as_ptr()).capabilities as _ })
+    }
+
+    /// Pixel formats supported by the codec, or `None` if unknown.
+    ///
+    /// Reads `AVCodec.pix_fmts`, which FFmpeg 7.1 deprecates in favour of
+    /// `avcodec_get_supported_config`, not available in FFmpeg 7.0. The `supported_*`
+    /// methods will need to move to it when the fields they read are removed.
+    pub fn supported_pixel_formats(&self) -> Option<Vec<PixelFormat>> {
+        unsafe {
+            terminated((*self.as_ptr()).pix_fmts, |&format| {
+                format != ffi::AVPixelFormat::AV_PIX_FMT_NONE
+            })
+            .map(|formats| formats.iter().map(|&format| format.into()).collect())
+        }
+    }
+
+    /// Sample formats supported by the codec, or `None` if unknown.
+    ///
+    /// Reads the deprecated `AVCodec.sample_fmts`, see [`Codec::supported_pixel_formats`].
+    pub fn supported_sample_formats(&self) -> Option<Vec<SampleFormat>> {
+        unsafe {
+            terminated((*self.as_ptr()).sample_fmts, |&format| {
+                format != ffi::AVSampleFormat::AV_SAMPLE_FMT_NONE
+            })
+            .map(|formats| formats.iter().map(|&format| format.into()).collect())
+        }
+    }
+
+    /// Sample rates supported by the codec, or `None` if any.
+    ///
+    /// Reads the deprecated `AVCodec.supported_samplerates`, see [`Codec::supported_pixel_formats`].
+    pub fn supported_sample_rates(&self) -> Option<Vec<u32>> {
+        unsafe {
+            terminated((*self.as_ptr()).supported_samplerates, |&rate| rate != 0)
+                .map(|rates| rates.iter().map(|&rate| rate as _).collect())
+        }
+    }
+
+    /// Channel layouts supported by the codec, or `None` if unknown.
+    ///
+    /// Reads the deprecated `AVCodec.ch_layouts`, see [`Codec::supported_pixel_formats`].
+    pub fn supported_channel_layouts(&self) -> Option<Vec<ChannelLayout>> {
+        unsafe {
+            terminated((*self.as_ptr()).ch_layouts, |layout| {
+                layout.nb_channels != 0
+            })
+            .map(|layouts| {
+                layouts
+                    .iter()
+                    .map(|layout| ChannelLayout::copy_from_ptr(layout))
+                    .collect()
+            })
+        }
+    }
+
+    /// Frame rates supported by the codec, or `None` if any.
+    ///
+    /// Reads the deprecated `AVCodec.supported_framerates`, see [`Codec::supported_pixel_formats`].
+    pub fn supported_frame_rates(&self) -> Option<Vec<Rational>> {
+        unsafe {
+            terminated((*self.as_ptr()).supported_framerates, |rate| {
+                rate.num != 0 || rate.den != 0
+            })
+            .map(|rates| rates.iter().map(|&rate| rate.into()).collect())
+        }
+    }
+
+    /// Profiles recognized by the codec, e.g. `Main` or `High` for H.264.
+    pub fn profiles(&self) -> Vec<Profile> {
+        unsafe {
+            terminated((*self.as_ptr()).profiles, |profile| {
+                profile.profile != ffi::AV_PROFILE_UNKNOWN
+            })
+            .unwrap_or_default()
+            .iter()
+            .map(|profile| Profile {
+                id: profile.profile,
+                name: CStr::from_ptr(profile.name).to_str().unwrap_or_default(),
+            })
+            .collect()
+        }
+    }
+}
+
+/// Slice of the elements of an array ending with a sentinel, or `None` if `ptr` is null.
+unsafe fn terminated<'a, T>(ptr: *const T, valid: impl Fn(&T) -> bool) -> Option<&'a [T]> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    let mut len = 0;
+    while valid(&*ptr.add(len)) {
+        len += 1;
+    }
+
+    Some(std::slice::from_raw_parts(ptr, len))
+}
+
+/// A profile of a codec, to be set on an encoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Profile {
+    id: i32,
+    name: &'static str,
+}
+
+impl Profile {
+    /// Value of the profile, e.g. `AV_PROFILE_H264_HIGH`.
+    #[inline]
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
@@ -1,7 +1,17 @@
-use std::rc::Rc;
+use std::{marker::PhantomData, mem::ManuallyDrop, ops::Deref, rc::Rc};
 
-use super::CodecId;
-use crate::{ffi, MediaType};
+use super::{CodecId, FieldOrder};
+use crate::{
+    channel::ChannelLayout,
+    color::{ChromaLocation, ColorPrimaries, ColorRange, ColorSpace, ColorTransferCharacteristic},
+    error::Error,
+    ffi,
+    pixel::PixelFormat,
+    sample::SampleFormat,
+    MediaType, Rational,
+};
+
+const PADDING_SIZE: usize = ffi::AV_INPUT_BUFFER_PADDING_SIZE as usize;
 
 pub struct CodecParameters<D> {
     ptr: *mut ffi::AVCodecParameters,
@@ -43,6 +53,126 @@ impl<D> CodecParameters<D> {
     pub fn codec_id(&self) -> CodecId {
         unsafe { (*self.as_ptr()).codec_id }.into()
     }
+
+    /// Container-specific codec identifier, e.g. a FourCC.
+    pub fn codec_tag(&self) -> u32 {
+        unsafe { (*self.as_ptr()).codec_tag }
+    }
+
+    /// Out-of-band codec data, e.g. the SPS and PPS of H.264 in MP4.
+    pub fn extradata(&self) -> Option<&[u8]> {
+        unsafe {
+            let par = self.as_ptr();
+            if (*par).extradata.is_null() {
+                None
+            } else {
+                Some(std::slice::from_raw_parts(
+                    (*par).extradata,
+                    (*par).extradata_size as _,
+                ))
+            }
+        }
+    }
+
+    pub fn bit_rate(&self) -> i64 {
+        unsafe { (*self.as_ptr()).bit_rate }
+    }
+
+    /// Codec-specific profile, e.g. `AV_PROFILE_H264_HIGH`.
+    pub fn profile(&self) -> i32 {
+        unsafe { (*self.as_ptr()).profile }
+    }
+
+    /// Codec-specific level, e.g. `41` for H.264 level 4.1.
+    pub fn level(&self) -> i32 {
+        unsafe { (*self.as_ptr()).level }
+    }
+
+    pub fn width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).width as _ }
+    }
+
+    pub fn height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).height as _ }
+    }
+
+    /// Pixel format of video streams, or [`PixelFormat::None`] for other streams.
+    pub fn pixel_format(&self) -> PixelFormat {
+        unsafe {
+            match (*self.as_ptr()).codec_type {
+                ffi::AVMediaType::AVMEDIA_TYPE_VIDEO => {
+                    std::mem::transmute::<i32, ffi::AVPixelFormat>((*self.as_ptr()).format).into()
+                }
+                _ => PixelFormat::None,
+            }
+        }
+    }
+
+    /// Sample format of audio streams, or [`SampleFormat::None`] for other streams.
+    pub fn sample_format(&self) -> SampleFormat {
+        unsafe {
+            match (*self.as_ptr()).codec_type {
+                ffi::AVMediaType::AVMEDIA_TYPE_AUDIO => (*self.as_ptr()).format.into(),
+                _ => SampleFormat::None,
+            }
+        }
+    }
+
+    pub fn sample_aspect_ratio(&self) -> Rational {
+        unsafe { (*self.as_ptr()).sample_aspect_ratio.into() }
+    }
+
+    pub fn field_order(&self) -> FieldOrder {
+        unsafe { (*self.as_ptr()).field_order.into() }
+    }
+
+    pub fn color_range(&self) -> ColorRange {
+        unsafe { (*self.as_ptr()).color_range.into() }
+    }
+
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        unsafe { (*self.as_ptr()).color_primaries.into() }
+    }
+
+    pub fn color_transfer_characteristic(&self) -> ColorTransferCharacteristic {
+        unsafe { (*self.as_ptr()).color_trc.into() }
+    }
+
+    pub fn color_space(&self) -> ColorSpace {
+        unsafe { (*self.as_ptr()).color_space.into() }
+    }
+
+    pub fn chroma_location(&self) -> ChromaLocation {
+        unsafe { (*self.as_ptr()).chroma_location.into() }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        unsafe { (*self.as_ptr()).sample_rate as _ }
+    }
+
+    pub fn channel_layout(&self) -> ChannelLayout {
+        unsafe { ChannelLayout::copy_from_ptr(&(*self.as_ptr()).ch_layout) }
+    }
+
+    /// Number of samples per channel in an audio frame, if constant.
+    pub fn frame_size(&self) -> u32 {
+        unsafe { (*self.as_ptr()).frame_size as _ }
+    }
+
+    /// Returns mutable access to the parameters.
+    ///
+    /// Parameters borrowed from a stream are copied first, like [`Clone`] does, so that
+    /// the stream and the other handles to its parameters are left untouched. Use
+    /// [`StreamMut::parameters_mut`] to change the parameters of an output stream.
+    ///
+    /// [`StreamMut::parameters_mut`]: crate::stream::StreamMut::parameters_mut
+    pub fn make_mut(&mut self) -> CodecParametersMut<'_> {
+        if self.dtor.is_some() {
+            *self = self.clone();
+        }
+
+        unsafe { CodecParametersMut::wrap(self.as_mut_ptr()) }
+    }
 }
 
 impl<D> Default for CodecParameters<D> {
@@ -74,3 +204,329 @@ impl<D> Clone for CodecParameters<D> {
         }
     }
 }
+
+/// Mutable access to codec parameters, see [`CodecParameters::make_mut`].
+///
+/// The parameters can also be read through [`Deref`].
+pub struct CodecParametersMut<'a> {
+    parameters: ManuallyDrop<CodecParameters<()>>,
+    _marker: PhantomData<&'a mut ffi::AVCodecParameters>,
+}
+
+impl CodecParametersMut<'_> {
+    /// `ptr` must not be accessed through anything else for `'a`.
+    pub(crate) unsafe fn wrap(ptr: *mut ffi::AVCodecParameters) -> Self {
+        CodecParametersMut {
+            parameters: ManuallyDrop::new(CodecParameters::wrap(ptr, None)),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn as_mut_ptr(&mut self) -> *mut ffi::AVCodecParameters {
+        self.parameters.as_mut_ptr()
+    }
+}
+
+impl CodecParametersMut<'_> {
+    pub fn set_codec_type(&mut self, codec_type: MediaType) {
+        unsafe {
+            (*self.as_mut_ptr()).codec_type = codec_type.into();
+        }
+    }
+
+    pub fn set_codec_id(&mut self, codec_id: CodecId) {
+        unsafe {
+            (*self.as_mut_ptr()).codec_id = codec_id.into();
+        }
+    }
+
+    pub fn set_codec_tag(&mut self, codec_tag: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).codec_tag = codec_tag;
+        }
+    }
+
+    pub fn set_extradata(&mut self, extradata: &[u8]) -> Result<(), Error> {
+        let size = libc::c_int::try_from(extradata.len()).map_err(|_| Error::InvalidArgument)?;
+
+        unsafe {
+            let ptr = ffi::av_mallocz(extradata.len() + PADDING_SIZE) as *mut u8;
+            if ptr.is_null() {
+                return Err(Error::OutOfMemory);
+            }
+            std::ptr::copy_nonoverlapping(extradata.as_ptr(), ptr, extradata.len());
+
+            let par = self.as_mut_ptr();
+            ffi::av_freep(&mut (*par).extradata as *mut *mut u8 as *mut _);
+            (*par).extradata = ptr;
+            (*par).extradata_size = size;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_bit_rate(&mut self, bit_rate: i64) {
+        unsafe {
+            (*self.as_mut_ptr()).bit_rate = bit_rate;
+        }
+    }
+
+    pub fn set_profile(&mut self, profile: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).profile = profile;
+        }
+    }
+
+    pub fn set_level(&mut self, level: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).level = level;
+        }
+    }
+
+    pub fn set_width(&mut self, width: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).width = width as _;
+        }
+    }
+
+    pub fn set_height(&mut self, height: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).height = height as _;
+        }
+    }
+
+    pub fn set_pixel_format(&mut self, format: PixelFormat) {
+        unsafe {
+            (*self.as_mut_ptr()).format = ffi::AVPixelFormat::from(format) as _;
+        }
+    }
+
+    pub fn set_sample_format(&mut self, format: SampleFormat) {
+        unsafe {
+            (*self.as_mut_ptr()).format = ffi::AVSampleFormat::from(format) as _;
+        }
+    }
+
+    pub fn set_sample_aspect_ratio<R: Into<Rational>>(&mut self, sample_aspect_ratio: R) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_aspect_ratio = sample_aspect_ratio.into().into();
+        }
+    }
+
+    pub fn set_field_order(&mut self, field_order: FieldOrder) {
+        unsafe {
+            (*self.as_mut_ptr()).field_order = field_order.into();
+        }
+    }
+
+    pub fn set_color_range(&mut self, color_range: ColorRange) {
+        unsafe {
+            (*self.as_mut_ptr()).color_range = color_range.into();
+        }
+    }
+
+    pub fn set_color_primaries(&mut self, color_primaries: ColorPrimaries) {
+        unsafe {
+            (*self.as_mut_ptr()).color_primaries = color_primaries.into();
+        }
+    }
+
+    pub fn set_color_transfer_characteristic(&mut self, transfer: ColorTransferCharacteristic) {
+        unsafe {
+            (*self.as_mut_ptr()).color_trc = transfer.into();
+        }
+    }
+
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        unsafe {
+            (*self.as_mut_ptr()).color_space = color_space.into();
+        }
+    }
+
+    pub fn set_chroma_location(&mut self, chroma_location: ChromaLocation) {
+        unsafe {
+            (*self.as_mut_ptr()).chroma_location = chroma_location.into();
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_rate = sample_rate as _;
+        }
+    }
+
+    pub fn set_channel_layout(&mut self, layout: &ChannelLayout) -> Result<(), Error> {
+        unsafe { layout.copy_to_ptr(&mut (*self.as_mut_ptr()).ch_layout) }
+    }
+
+    pub fn set_frame_size(&mut self, frame_size: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).frame_size = frame_size as _;
+        }
+    }
+}
+
+impl Deref for CodecParametersMut<'_> {
+    type Target = CodecParameters<()>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.parameters
+    }
+}
+
+/// Builds [`CodecParameters`] from scratch, e.g. for an output stream that is not
+/// copied from an input stream.
+pub struct CodecParametersBuilder {
+    parameters: CodecParameters<()>,
+    error: Option<Error>,
+}
+
+impl CodecParametersBuilder {
+    /// Starts building parameters for `codec_id`.
+    pub fn new(codec_id: CodecId) -> Self {
+        let mut parameters = CodecParameters::new();
+        let mut par = parameters.make_mut();
+        par.set_codec_type(codec_id.medium());
+        par.set_codec_id(codec_id);
+
+        CodecParametersBuilder {
+            parameters,
+            error: None,
+        }
+    }
+
+    pub fn codec_tag(mut self, codec_tag: u32) -> Self {
+        self.parameters.make_mut().set_codec_tag(codec_tag);
+        self
+    }
+
+    pub fn extradata(mut self, extradata: &[u8]) -> Self {
+        if let Err(e) = self.parameters.make_mut().set_extradata(extradata) {
+            self.error.get_or_insert(e);
+        }
+        self
+    }
+
+    pub fn bit_rate(mut self, bit_rate: i64) -> Self {
+        self.parameters.make_mut().set_bit_rate(bit_rate);
+        self
+    }
+
+    pub fn profile(mut self, profile: i32) -> Self {
+        self.parameters.make_mut().set_profile(profile);
+        self
+    }
+
+    pub fn level(mut self, level: i32) -> Self {
+        self.parameters.make_mut().set_level(level);
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.parameters.make_mut().set_width(width);
+        self.parameters.make_mut().set_height(height);
+        self
+    }
+
+    pub fn pixel_format(mut self, format: PixelFormat) -> Self {
+        self.parameters.make_mut().set_pixel_format(format);
+        self
+    }
+
+    pub fn sample_aspect_ratio<R: Into<Rational>>(mut self, sample_aspect_ratio: R) -> Self {
+        self.parameters
+            .make_mut()
+            .set_sample_aspect_ratio(sample_aspect_ratio);
+        self
+    }
+
+    pub fn field_order(mut self, field_order: FieldOrder) -> Self {
+        self.parameters.make_mut().set_field_order(field_order);
+        self
+    }
+
+    pub fn color_range(mut self, color_range: ColorRange) -> Self {
+        self.parameters.make_mut().set_color_range(color_range);
+        self
+    }
+
+    pub fn color_primaries(mut self, color_primaries: ColorPrimaries) -> Self {
+        self.parameters
+            .make_mut()
+            .set_color_primaries(color_primaries);
+        self
+    }
+
+    pub fn color_transfer_characteristic(mut self, transfer: ColorTransferCharacteristic) -> Self {
+        self.parameters
+            .make_mut()
+            .set_color_transfer_characteristic(transfer);
+        self
+    }
+
+    pub fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.parameters.make_mut().set_color_space(color_space);
+        self
+    }
+
+    pub fn chroma_location(mut self, chroma_location: ChromaLocation) -> Self {
+        self.parameters
+            .make_mut()
+            .set_chroma_location(chroma_location);
+        self
+    }
+
+    pub fn sample_format(mut self, format: SampleFormat) -> Self {
+        self.parameters.make_mut().set_sample_format(format);
+        self
+    }
+
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.parameters.make_mut().set_sample_rate(sample_rate);
+        self
+    }
+
+    pub fn channel_layout(mut self, layout: &ChannelLayout) -> Self {
+        if let Err(e) = self.parameters.make_mut().set_channel_layout(layout) {
+            self.error.get_or_insert(e);
+        }
+        self
+    }
+
+    pub fn frame_size(mut self, frame_size: u32) -> Self {
+        self.parameters.make_mut().set_frame_size(frame_size);
+        self
+    }
+
+    /// Returns the parameters, or the first error met while setting them.
+    pub fn build<D>(self) -> Result<CodecParameters<D>, Error> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        let mut parameters = std::mem::ManuallyDrop::new(self.parameters);
+        unsafe { Ok(CodecParameters::wrap(parameters.as_mut_ptr(), None)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let parameters: CodecParameters<()> = CodecParametersBuilder::new(CodecId::H264)
+            .size(1920, 1080)
+            .pixel_format(PixelFormat::YUV420P)
+            .extradata(&[0, 0, 0, 1])
+            .build()
+            .unwrap();
+
+        assert_eq!(parameters.codec_type(), MediaType::Video);
+        assert_eq!(parameters.width(), 1920);
+        assert_eq!(parameters.pixel_format(), PixelFormat::YUV420P);
+        assert_eq!(parameters.sample_format(), SampleFormat::None);
+        assert_eq!(parameters.extradata(), Some(&[0, 0, 0, 1][..]));
+    }
+}
@@ -0,0 +1,172 @@
+use super::{CodecId, Context};
+use crate::{
+    error::Error,
+    ffi,
+    packet::{Flags, Packet},
+};
+
+const PADDING_SIZE: usize = ffi::AV_INPUT_BUFFER_PADDING_SIZE as usize;
+
+/// Splits a raw elementary stream into packets, e.g. for H.264 Annex B or ADTS AAC
+/// data that is not wrapped in any container.
+pub struct Parser {
+    ptr: *mut ffi::AVCodecParserContext,
+    context: Context,
+    buf: Vec<u8>,
+}
+
+unsafe impl Send for Parser {}
+
+impl Parser {
+    pub fn new(id: CodecId) -> Result<Self, Error> {
+        unsafe {
+            let ptr = ffi::av_parser_init(ffi::AVCodecID::from(id) as _);
+
+            if ptr.is_null() {
+                return Err(Error::ParserNotFound);
+            }
+
+            let mut context = Context::new();
+            (*context.as_mut_ptr()).codec_id = id.into();
+
+            Ok(Parser {
+                ptr,
+                context,
+                buf: Vec::new(),
+            })
+        }
+    }
+
+    /// Parses a chunk of data of arbitrary size, returning the packets it completes.
+    pub fn parse(&mut self, data: &[u8]) -> Result<Vec<Packet>, Error> {
+        self.parse_with_timestamps(data, None, None, -1)
+    }
+
+    /// Same as [`Parser::parse`], with the timestamps and byte position of the chunk,
+    /// which are attached to the packet starting in it.
+    pub fn parse_with_timestamps(
+        &mut self,
+        data: &[u8],
+        pts: Option<i64>,
+        dts: Option<i64>,
+        pos: i64,
+    ) -> Result<Vec<Packet>, Error> {
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.clear();
+        buf.extend_from_slice(data);
+        buf.resize(data.len() + PADDING_SIZE, 0);
+
+        let mut packets = Vec::new();
+        let mut offset = 0;
+        let mut pts = pts.unwrap_or(ffi::AV_NOPTS_VALUE);
+        let mut dts = dts.unwrap_or(ffi::AV_NOPTS_VALUE);
+        let mut pos = pos;
+
+        let ret = loop {
+            if offset >= data.len() {
+                break Ok(packets);
+            }
+
+            let remaining = &buf[offset..data.len()];
+            let (consumed, packet) = match unsafe { self.parse_raw(remaining, pts, dts, pos) } {
+                Ok(ret) => ret,
+                Err(e) => break Err(e),
+            };
+
+            offset += consumed;
+            pts = ffi::AV_NOPTS_VALUE;
+            dts = ffi::AV_NOPTS_VALUE;
+            pos = -1;
+
+            match packet {
+                Some(packet) => packets.push(packet),
+                None if consumed == 0 => break Ok(packets),
+                None => {}
+            }
+        };
+
+        self.buf = buf;
+        ret
+    }
+
+    /// Signals the end of the stream, returning the packets still buffered in the parser.
+    pub fn flush(&mut self) -> Result<Vec<Packet>, Error> {
+        let mut packets = Vec::new();
+
+        loop {
+            let (_, packet) =
+                unsafe { self.parse_raw(&[], ffi::AV_NOPTS_VALUE, ffi::AV_NOPTS_VALUE, -1)? };
+
+            match packet {
+                Some(packet) => packets.push(packet),
+                None => return Ok(packets),
+            }
+        }
+    }
+
+    /// `data` must be followed by `AV_INPUT_BUFFER_PADDING_SIZE` readable bytes, unless empty.
+    unsafe fn parse_raw(
+        &mut self,
+        data: &[u8],
+        pts: i64,
+        dts: i64,
+        pos: i64,
+    ) -> Result<(usize, Option<Packet>), Error> {
+        let mut out = std::ptr::null_mut();
+        let mut out_size = 0;
+        let (buf, buf_size) = if data.is_empty() {
+            (std::ptr::null(), 0)
+        } else {
+            (data.as_ptr(), data.len() as _)
+        };
+
+        let consumed = ffi::av_parser_parse2(
+            self.ptr,
+            self.context.as_mut_ptr(),
+            &mut out,
+            &mut out_size,
+            buf,
+            buf_size,
+            pts,
+            dts,
+            pos,
+        );
+
+        if consumed < 0 {
+            return Err(Error::from_ffmpeg_error_code(consumed));
+        }
+
+        if out_size <= 0 {
+            return Ok((consumed as _, None));
+        }
+
+        let mut packet = Packet::try_new(out_size as _)?;
+        if let Some(data) = packet.data_mut() {
+            data.copy_from_slice(std::slice::from_raw_parts(out, out_size as _));
+        }
+
+        let parser = &*self.ptr;
+        packet.set_pts(Some(parser.pts).filter(|&pts| pts != ffi::AV_NOPTS_VALUE));
+        packet.set_dts(Some(parser.dts).filter(|&dts| dts != ffi::AV_NOPTS_VALUE));
+        packet.set_pos(parser.pos);
+        packet.set_duration(parser.duration as _);
+        if parser.key_frame == 1 {
+            packet.set_flags(packet.flags() | Flags::KEY);
+        }
+
+        Ok((consumed as _, Some(packet)))
+    }
+
+    /// Codec context updated by the parser, e.g. with the dimensions of a video stream.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+}
+
+impl Drop for Parser {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::av_parser_close(self.ptr);
+        }
+    }
+}
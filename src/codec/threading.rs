@@ -0,0 +1,9 @@
+use crate::ffi;
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct ThreadType: i32 {
+        const FRAME = ffi::FF_THREAD_FRAME;
+        const SLICE = ffi::FF_THREAD_SLICE;
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    ffi::{CStr, CString},
+    str::FromStr,
+};
+
+use crate::{error::Error, ffi};
+
+/// Range of the values of video samples.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ColorRange {
+    Unspecified,
+    /// Limited range, e.g. 16-235 for 8-bit luma, also called TV or MPEG range.
+    Limited,
+    /// Full range, e.g. 0-255 for 8 bits, also called PC or JPEG range.
+    Full,
+}
+
+impl From<ffi::AVColorRange> for ColorRange {
+    fn from(value: ffi::AVColorRange) -> Self {
+        match value {
+            ffi::AVColorRange::AVCOL_RANGE_UNSPECIFIED => ColorRange::Unspecified,
+            ffi::AVColorRange::AVCOL_RANGE_MPEG => ColorRange::Limited,
+            ffi::AVColorRange::AVCOL_RANGE_JPEG => ColorRange::Full,
+            ffi::AVColorRange::AVCOL_RANGE_NB => ColorRange::Unspecified,
+        }
+    }
+}
+
+impl From<ColorRange> for ffi::AVColorRange {
+    fn from(value: ColorRange) -> ffi::AVColorRange {
+        match value {
+            ColorRange::Unspecified => ffi::AVColorRange::AVCOL_RANGE_UNSPECIFIED,
+            ColorRange::Limited => ffi::AVColorRange::AVCOL_RANGE_MPEG,
+            ColorRange::Full => ffi::AVColorRange::AVCOL_RANGE_JPEG,
+        }
+    }
+}
+
+/// Chromaticity coordinates of the source primaries (ISO/IEC 23091-2 ColourPrimaries).
+#[allow(non_camel_case_types)]
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ColorPrimaries {
+    Reserved0,
+    BT709,
+    Unspecified,
+    Reserved,
+    BT470M,
+    BT470BG,
+    SMPTE170M,
+    SMPTE240M,
+    Film,
+    BT2020,
+    SMPTE428,
+    SMPTE431,
+    SMPTE432,
+    EBU3213,
+}
+
+impl From<ffi::AVColorPrimaries> for ColorPrimaries {
+    fn from(value: ffi::AVColorPrimaries) -> Self {
+        match value {
+            ffi::AVColorPrimaries::AVCOL_PRI_RESERVED0 => ColorPrimaries::Reserved0,
+            ffi::AVColorPrimaries::AVCOL_PRI_BT709 => ColorPrimaries::BT709,
+            ffi::AVColorPrimaries::AVCOL_PRI_UNSPECIFIED => ColorPrimaries::Unspecified,
+            ffi::AVColorPrimaries::AVCOL_PRI_RESERVED => ColorPrimaries::Reserved,
+            ffi::AVColorPrimaries::AVCOL_PRI_BT470M => ColorPrimaries::BT470M,
+            ffi::AVColorPrimaries::AVCOL_PRI_BT470BG => ColorPrimaries::BT470BG,
+            ffi::AVColorPrimaries::AVCOL_PRI_SMPTE170M => ColorPrimaries::SMPTE170M,
+            ffi::AVColorPrimaries::AVCOL_PRI_SMPTE240M => ColorPrimaries::SMPTE240M,
+            ffi::AVColorPrimaries::AVCOL_PRI_FILM => ColorPrimaries::Film,
+            ffi::AVColorPrimaries::AVCOL_PRI_BT2020 => ColorPrimaries::BT2020,
+            ffi::AVColorPrimaries::AVCOL_PRI_SMPTE428 => ColorPrimaries::SMPTE428,
+            ffi::AVColorPrimaries::AVCOL_PRI_SMPTE431 => ColorPrimaries::SMPTE431,
+            ffi::AVColorPrimaries::AVCOL_PRI_SMPTE432 => ColorPrimaries::SMPTE432,
+            ffi::AVColorPrimaries::AVCOL_PRI_EBU3213 => ColorPrimaries::EBU3213,
+            ffi::AVColorPrimaries::AVCOL_PRI_NB => ColorPrimaries::Unspecified,
+        }
+    }
+}
+
+impl From<ColorPrimaries> for ffi::AVColorPrimaries {
+    fn from(value: ColorPrimaries) -> ffi::AVColorPrimaries {
+        match value {
+            ColorPrimaries::Reserved0 => ffi::AVColorPrimaries::AVCOL_PRI_RESERVED0,
+            ColorPrimaries::BT709 => ffi::AVColorPrimaries::AVCOL_PRI_BT709,
+            ColorPrimaries::Unspecified => ffi::AVColorPrimaries::AVCOL_PRI_UNSPECIFIED,
+            ColorPrimaries::Reserved => ffi::AVColorPrimaries::AVCOL_PRI_RESERVED,
+            ColorPrimaries::BT470M => ffi::AVColorPrimaries::AVCOL_PRI_BT470M,
+            ColorPrimaries::BT470BG => ffi::AVColorPrimaries::AVCOL_PRI_BT470BG,
+            ColorPrimaries::SMPTE170M => ffi::AVColorPrimaries::AVCOL_PRI_SMPTE170M,
+            ColorPrimaries::SMPTE240M => ffi::AVColorPrimaries::AVCOL_PRI_SMPTE240M,
+            ColorPrimaries::Film => ffi::AVColorPrimaries::AVCOL_PRI_FILM,
+            ColorPrimaries::BT2020 => ffi::AVColorPrimaries::AVCOL_PRI_BT2020,
+            ColorPrimaries::SMPTE428 => ffi::AVColorPrimaries::AVCOL_PRI_SMPTE428,
+            ColorPrimaries::SMPTE431 => ffi::AVColorPrimaries::AVCOL_PRI_SMPTE431,
+            ColorPrimaries::SMPTE432 => ffi::AVColorPrimaries::AVCOL_PRI_SMPTE432,
+            ColorPrimaries::EBU3213 => ffi::AVColorPrimaries::AVCOL_PRI_EBU3213,
+        }
+    }
+}
+
+/// Transfer characteristic, e.g. PQ (`SMPTE2084`) or HLG (`ARIB_STD_B67`) for HDR.
+#[allow(non_camel_case_types)]
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ColorTransferCharacteristic {
+    Reserved0,
+    BT709,
+    Unspecified,
+    Reserved,
+    Gamma22,
+    Gamma28,
+    SMPTE170M,
+    SMPTE240M,
+    Linear,
+    Log,
+    LogSqrt,
+    IEC61966_2_4,
+    BT1361_ECG,
+    IEC61966_2_1,
+    BT2020_10,
+    BT2020_12,
+    SMPTE2084,
+    SMPTE428,
+    ARIB_STD_B67,
+}
+
+impl From<ffi::AVColorTransferCharacteristic> for ColorTransferCharacteristic {
+    fn from(value: ffi::AVColorTransferCharacteristic) -> Self {
+        use ffi::AVColorTransferCharacteristic::*;
+
+        match value {
+            AVCOL_TRC_RESERVED0 => ColorTransferCharacteristic::Reserved0,
+            AVCOL_TRC_BT709 => ColorTransferCharacteristic::BT709,
+            AVCOL_TRC_UNSPECIFIED => ColorTransferCharacteristic::Unspecified,
+            AVCOL_TRC_RESERVED => ColorTransferCharacteristic::Reserved,
+            AVCOL_TRC_GAMMA22 => ColorTransferCharacteristic::Gamma22,
+            AVCOL_TRC_GAMMA28 => ColorTransferCharacteristic::Gamma28,
+            AVCOL_TRC_SMPTE170M => ColorTransferCharacteristic::SMPTE170M,
+            AVCOL_TRC_SMPTE240M => ColorTransferCharacteristic::SMPTE240M,
+            AVCOL_TRC_LINEAR => ColorTransferCharacteristic::Linear,
+            AVCOL_TRC_LOG => ColorTransferCharacteristic::Log,
+            AVCOL_TRC_LOG_SQRT => ColorTransferCharacteristic::LogSqrt,
+            AVCOL_TRC_IEC61966_2_4 => ColorTransferCharacteristic::IEC61966_2_4,
+            AVCOL_TRC_BT1361_ECG => ColorTransferCharacteristic::BT1361_ECG,
+            AVCOL_TRC_IEC61966_2_1 => ColorTransferCharacteristic::IEC61966_2_1,
+            AVCOL_TRC_BT2020_10 => ColorTransferCharacteristic::BT2020_10,
+            AVCOL_TRC_BT2020_12 => ColorTransferCharacteristic::BT2020_12,
+            AVCOL_TRC_SMPTE2084 => ColorTransferCharacteristic::SMPTE2084,
+            AVCOL_TRC_SMPTE428 => ColorTransferCharacteristic::SMPTE428,
+            AVCOL_TRC_ARIB_STD_B67 => ColorTransferCharacteristic::ARIB_STD_B67,
+            AVCOL_TRC_NB => ColorTransferCharacteristic::Unspecified,
+        }
+    }
+}
+
+impl From<ColorTransferCharacteristic> for ffi::AVColorTransferCharacteristic {
+    fn from(value: ColorTransferCharacteristic) -> ffi::AVColorTransferCharacteristic {
+        use ffi::AVColorTransferCharacteristic::*;
+
+        match value {
+            ColorTransferCharacteristic::Reserved0 => AVCOL_TRC_RESERVED0,
+            ColorTransferCharacteristic::BT709 => AVCOL_TRC_BT709,
+            ColorTransferCharacteristic::Unspecified => AVCOL_TRC_UNSPECIFIED,
+            ColorTransferCharacteristic::Reserved => AVCOL_TRC_RESERVED,
+            ColorTransferCharacteristic::Gamma22 => AVCOL_TRC_GAMMA22,
+            ColorTransferCharacteristic::Gamma28 => AVCOL_TRC_GAMMA28,
+            ColorTransferCharacteristic::SMPTE170M => AVCOL_TRC_SMPTE170M,
+            ColorTransferCharacteristic::SMPTE240M => AVCOL_TRC_SMPTE240M,
+            ColorTransferCharacteristic::Linear => AVCOL_TRC_LINEAR,
+            ColorTransferCharacteristic::Log => AVCOL_TRC_LOG,
+            ColorTransferCharacteristic::LogSqrt => AVCOL_TRC_LOG_SQRT,
+            ColorTransferCharacteristic::IEC61966_2_4 => AVCOL_TRC_IEC61966_2_4,
+            ColorTransferCharacteristic::BT1361_ECG => AVCOL_TRC_BT1361_ECG,
+            ColorTransferCharacteristic::IEC61966_2_1 => AVCOL_TRC_IEC61966_2_1,
+            ColorTransferCharacteristic::BT2020_10 => AVCOL_TRC_BT2020_10,
+            ColorTransferCharacteristic::BT2020_12 => AVCOL_TRC_BT2020_12,
+            ColorTransferCharacteristic::SMPTE2084 => AVCOL_TRC_SMPTE2084,
+            ColorTransferCharacteristic::SMPTE428 => AVCOL_TRC_SMPTE428,
+            ColorTransferCharacteristic::ARIB_STD_B67 => AVCOL_TRC_ARIB_STD_B67,
+        }
+    }
+}
+
+/// YUV to RGB conversion matrix (ISO/IEC 23091-2 MatrixCoefficients).
+#[allow(non_camel_case_types)]
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ColorSpace {
+    RGB,
+    BT709,
+    Unspecified,
+    Reserved,
+    FCC,
+    BT470BG,
+    SMPTE170M,
+    SMPTE240M,
+    YCGCO,
+    BT2020_NCL,
+    BT2020_CL,
+    SMPTE2085,
+    ChromaDerivedNCL,
+    ChromaDerivedCL,
+    ICTCP,
+}
+
+impl From<ffi::AVColorSpace> for ColorSpace {
+    fn from(value: ffi::AVColorSpace) -> Self {
+        match value {
+            ffi::AVColorSpace::AVCOL_SPC_RGB => ColorSpace::RGB,
+            ffi::AVColorSpace::AVCOL_SPC_BT709 => ColorSpace::BT709,
+            ffi::AVColorSpace::AVCOL_SPC_UNSPECIFIED => ColorSpace::Unspecified,
+            ffi::AVColorSpace::AVCOL_SPC_RESERVED => ColorSpace::Reserved,
+            ffi::AVColorSpace::AVCOL_SPC_FCC => ColorSpace::FCC,
+            ffi::AVColorSpace::AVCOL_SPC_BT470BG => ColorSpace::BT470BG,
+            ffi::AVColorSpace::AVCOL_SPC_SMPTE170M => ColorSpace::SMPTE170M,
+            ffi::AVColorSpace::AVCOL_SPC_SMPTE240M => ColorSpace::SMPTE240M,
+            ffi::AVColorSpace::AVCOL_SPC_YCGCO => ColorSpace::YCGCO,
+            ffi::AVColorSpace::AVCOL_SPC_BT2020_NCL => ColorSpace::BT2020_NCL,
+            ffi::AVColorSpace::AVCOL_SPC_BT2020_CL => ColorSpace::BT2020_CL,
+            ffi::AVColorSpace::AVCOL_SPC_SMPTE2085 => ColorSpace::SMPTE2085,
+            ffi::AVColorSpace::AVCOL_SPC_CHROMA_DERIVED_NCL => ColorSpace::ChromaDerivedNCL,
+            ffi::AVColorSpace::AVCOL_SPC_CHROMA_DERIVED_CL => ColorSpace::ChromaDerivedCL,
+            ffi::AVColorSpace::AVCOL_SPC_ICTCP => ColorSpace::ICTCP,
+            ffi::AVColorSpace::AVCOL_SPC_NB => ColorSpace::Unspecified,
+        }
+    }
+}
+
+impl From<ColorSpace> for ffi::AVColorSpace {
+    fn from(value: ColorSpace) -> ffi::AVColorSpace {
+        match value {
+            ColorSpace::RGB => ffi::AVColorSpace::AVCOL_SPC_RGB,
+            ColorSpace::BT709 => ffi::AVColorSpace::AVCOL_SPC_BT709,
+            ColorSpace::Unspecified => ffi::AVColorSpace::AVCOL_SPC_UNSPECIFIED,
+            ColorSpace::Reserved => ffi::AVColorSpace::AVCOL_SPC_RESERVED,
+            ColorSpace::FCC => ffi::AVColorSpace::AVCOL_SPC_FCC,
+            ColorSpace::BT470BG => ffi::AVColorSpace::AVCOL_SPC_BT470BG,
+            ColorSpace::SMPTE170M => ffi::AVColorSpace::AVCOL_SPC_SMPTE170M,
+            ColorSpace::SMPTE240M => ffi::AVColorSpace::AVCOL_SPC_SMPTE240M,
+            ColorSpace::YCGCO => ffi::AVColorSpace::AVCOL_SPC_YCGCO,
+            ColorSpace::BT2020_NCL => ffi::AVColorSpace::AVCOL_SPC_BT2020_NCL,
+            ColorSpace::BT2020_CL => ffi::AVColorSpace::AVCOL_SPC_BT2020_CL,
+            ColorSpace::SMPTE2085 => ffi::AVColorSpace::AVCOL_SPC_SMPTE2085,
+            ColorSpace::ChromaDerivedNCL => ffi::AVColorSpace::AVCOL_SPC_CHROMA_DERIVED_NCL,
+            ColorSpace::ChromaDerivedCL => ffi::AVColorSpace::AVCOL_SPC_CHROMA_DERIVED_CL,
+            ColorSpace::ICTCP => ffi::AVColorSpace::AVCOL_SPC_ICTCP,
+        }
+    }
+}
+
+/// Location of chroma samples relative to luma samples.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ChromaLocation {
+    Unspecified,
+    /// MPEG-2/4 4:2:0, H.264 default for 4:2:0.
+    Left,
+    /// MPEG-1 4:2:0, JPEG 4:2:0, H.263 4:2:0.
+    Center,
+    /// ITU-R 601, SMPTE 274M 296M S314M(DV 4:1:1), mpeg2 4:2:2.
+    TopLeft,
+    Top,
+    BottomLeft,
+    Bottom,
+}
+
+impl From<ffi::AVChromaLocation> for ChromaLocation {
+    fn from(value: ffi::AVChromaLocation) -> Self {
+        match value {
+            ffi::AVChromaLocation::AVCHROMA_LOC_UNSPECIFIED => ChromaLocation::Unspecified,
+            ffi::AVChromaLocation::AVCHROMA_LOC_LEFT => ChromaLocation::Left,
+            ffi::AVChromaLocation::AVCHROMA_LOC_CENTER => ChromaLocation::Center,
+            ffi::AVChromaLocation::AVCHROMA_LOC_TOPLEFT => ChromaLocation::TopLeft,
+            ffi::AVChromaLocation::AVCHROMA_LOC_TOP => ChromaLocation::Top,
+            ffi::AVChromaLocation::AVCHROMA_LOC_BOTTOMLEFT => ChromaLocation::BottomLeft,
+            ffi::AVChromaLocation::AVCHROMA_LOC_BOTTOM => ChromaLocation::Bottom,
+            ffi::AVChromaLocation::AVCHROMA_LOC_NB => ChromaLocation::Unspecified,
+        }
+    }
+}
+
+impl From<ChromaLocation> for ffi::AVChromaLocation {
+    fn from(value: ChromaLocation) -> ffi::AVChromaLocation {
+        match value {
+            ChromaLocation::Unspecified => ffi::AVChromaLocation::AVCHROMA_LOC_UNSPECIFIED,
+            ChromaLocation::Left => ffi::AVChromaLocation::AVCHROMA_LOC_LEFT,
+            ChromaLocation::Center => ffi::AVChromaLocation::AVCHROMA_LOC_CENTER,
+            ChromaLocation::TopLeft => ffi::AVChromaLocation::AVCHROMA_LOC_TOPLEFT,
+            ChromaLocation::Top => ffi::AVChromaLocation::AVCHROMA_LOC_TOP,
+            ChromaLocation::BottomLeft => ffi::AVChromaLocation::AVCHROMA_LOC_BOTTOMLEFT,
+            ChromaLocation::Bottom => ffi::AVChromaLocation::AVCHROMA_LOC_BOTTOM,
+        }
+    }
+}
+
+/// Implements `name`, `Display` and `FromStr` with the `av_*_name` and `av_*_from_name`
+/// functions of FFmpeg, which use the names of the specifications, e.g. `bt709` or `smpte2084`.
+macro_rules! impl_names {
+    ($ty:ty, $ffi_ty:ty, $name:ident, $from_name:ident) => {
+        impl $ty {
+            pub fn name(self) -> Option<&'static str> {
+                unsafe {
+                    let ptr = ffi::$name(self.into());
+                    if ptr.is_null() {
+                        None
+                    } else {
+                        CStr::from_ptr(ptr).to_str().ok()
+                    }
+                }
+            }
+        }
+
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str(self.name().unwrap_or("unknown"))
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Error> {
+                let name = CString::new(s).map_err(|_| Error::InvalidArgument)?;
+
+                unsafe {
+                    match ffi::$from_name(name.as_ptr()) {
+                        e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                        // A valid value of the enum, as returned by FFmpeg.
+                        value => Ok(std::mem::transmute::<i32, $ffi_ty>(value).into()),
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_names!(
+    ColorRange,
+    ffi::AVColorRange,
+    av_color_range_name,
+    av_color_range_from_name
+);
+impl_names!(
+    ColorPrimaries,
+    ffi::AVColorPrimaries,
+    av_color_primaries_name,
+    av_color_primaries_from_name
+);
+impl_names!(
+    ColorTransferCharacteristic,
+    ffi::AVColorTransferCharacteristic,
+    av_color_transfer_name,
+    av_color_transfer_from_name
+);
+impl_names!(
+    ColorSpace,
+    ffi::AVColorSpace,
+    av_color_space_name,
+    av_color_space_from_name
+);
+impl_names!(
+    ChromaLocation,
+    ffi::AVChromaLocation,
+    av_chroma_location_name,
+    av_chroma_location_from_name
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(ColorRange::Limited.to_string(), "tv");
+        assert_eq!("pc".parse::<ColorRange>().unwrap(), ColorRange::Full);
+        assert_eq!(
+            "smpte2084".parse::<ColorTransferCharacteristic>().unwrap(),
+            ColorTransferCharacteristic::SMPTE2084
+        );
+        assert_eq!(ColorSpace::BT2020_NCL.name(), Some("bt2020nc"));
+        assert!("foo".parse::<ColorPrimaries>().is_err());
+    }
+}
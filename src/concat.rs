@@ -0,0 +1,240 @@
+use crate::{
+    channel::ChannelLayout,
+    codec::CodecId,
+    container::{InputContainer, OutputContainer},
+    error::Error,
+    ffi,
+    packet::Packet,
+    remux::{map_streams, RemuxOptions},
+    MediaType, Rational,
+};
+
+/// Concatenates several inputs with identical stream layouts into one output,
+/// without re-encoding.
+///
+/// Timestamps of every input are shifted by the duration of the inputs before it,
+/// so that they stay continuous and monotonic in the output.
+pub struct Concat {
+    inputs: Vec<InputContainer>,
+}
+
+impl Concat {
+    pub fn new() -> Self {
+        Concat { inputs: Vec::new() }
+    }
+
+    pub fn input(mut self, input: InputContainer) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    /// Checks that every input has the same streams as the first one, with
+    /// compatible codec parameters.
+    pub fn check(&self) -> Result<(), Error> {
+        let first = self.inputs.first().ok_or(Error::NoInput)?;
+
+        for (index, input) in self.inputs.iter().enumerate().skip(1) {
+            if input.nb_streams() != first.nb_streams() {
+                return Err(Error::IncompatibleInput {
+                    index,
+                    reason: format!(
+                        "number of streams differs ({} vs {})",
+                        input.nb_streams(),
+                        first.nb_streams()
+                    ),
+                });
+            }
+
+            for (a, b) in first.streams().zip(input.streams()) {
+                let reason =
+                    unsafe { compare_parameters((*a.as_ptr()).codecpar, (*b.as_ptr()).codecpar) };
+
+                if let Some(reason) = reason {
+                    return Err(Error::IncompatibleInput {
+                        index,
+                        reason: format!("stream {}: {}", a.index(), reason),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Muxes all inputs sequentially into `output`, and writes the trailer of `output`.
+    pub fn run(mut self, output: &mut OutputContainer) -> Result<(), Error> {
+        self.check()?;
+
+        let options = RemuxOptions::new().copy_chapters(false);
+        let mapping = map_streams(&self.inputs[0], output, &options)?;
+        let time_bases: Vec<Rational> = self.inputs[0].streams().map(|s| s.time_base()).collect();
+        let mut last_dts: Vec<Option<i64>> = vec![None; mapping.len()];
+
+        // Start of the current input in the output, in `AV_TIME_BASE` units.
+        let mut offset = 0;
+
+        for input in self.inputs.iter_mut() {
+            let start = input.start_time().unwrap_or(0);
+            let mut end = 0;
+
+            loop {
+                let mut packet = Packet::empty();
+
+                match packet.read_from(input) {
+                    Ok(()) => {}
+                    Err(Error::Eof) => break,
+                    Err(e) => return Err(e),
+                }
+
+                let index = packet.stream_index() as usize;
+                let ost_index = match mapping.get(index) {
+                    Some(Some(ost_index)) => *ost_index,
+                    _ => continue,
+                };
+
+                let src_time_base = match input.stream(index as u32) {
+                    Some(ist) => ist.time_base(),
+                    None => continue,
+                };
+                let dst_time_base = time_bases[index];
+                let rebase = |ts: i64| unsafe {
+                    ffi::av_rescale_q(ts, src_time_base.into(), dst_time_base.into())
+                        + ffi::av_rescale_q(
+                            offset - start,
+                            ffi::AV_TIME_BASE_Q,
+                            dst_time_base.into(),
+                        )
+                };
+
+                let mut pts = packet.pts().map(rebase);
+                let mut dts = packet.dts().map(rebase);
+                let duration = unsafe {
+                    ffi::av_rescale_q(
+                        packet.duration(),
+                        src_time_base.into(),
+                        dst_time_base.into(),
+                    )
+                };
+
+                if let (Some(d), Some(last)) = (dts, last_dts[index]) {
+                    if d <= last {
+                        dts = Some(last + 1);
+                        pts = pts.map(|pts| pts.max(last + 1));
+                    }
+                }
+                if dts.is_some() {
+                    last_dts[index] = dts;
+                }
+
+                if let Some(ts) = pts.or(dts) {
+                    let ts = unsafe {
+                        ffi::av_rescale_q(ts + duration, dst_time_base.into(), ffi::AV_TIME_BASE_Q)
+                    };
+                    end = end.max(ts - offset);
+                }
+
+                packet.set_pts(pts);
+                packet.set_dts(dts);
+                packet.set_duration(duration);
+                packet.set_time_base(dst_time_base);
+                packet.set_stream_index(ost_index);
+                packet.set_pos(-1);
+
+                output.mux(packet)?;
+            }
+
+            offset += end;
+        }
+
+        output.write_trailer()
+    }
+}
+
+impl Default for Concat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe fn compare_parameters(
+    a: *const ffi::AVCodecParameters,
+    b: *const ffi::AVCodecParameters,
+) -> Option<String> {
+    let a = &*a;
+    let b = &*b;
+
+    if a.codec_type != b.codec_type {
+        return Some(format!(
+            "media type differs ({:?} vs {:?})",
+            MediaType::from(a.codec_type),
+            MediaType::from(b.codec_type)
+        ));
+    }
+
+    if a.codec_id != b.codec_id {
+        return Some(format!(
+            "codec differs ({:?} vs {:?})",
+            CodecId::from(a.codec_id),
+            CodecId::from(b.codec_id)
+        ));
+    }
+
+    match MediaType::from(a.codec_type) {
+        MediaType::Video => {
+            if a.width != b.width || a.height != b.height {
+                return Some(format!(
+                    "resolution differs ({}x{} vs {}x{})",
+                    a.width, a.height, b.width, b.height
+                ));
+            }
+
+            if a.format != b.format {
+                return Some("pixel format differs".into());
+            }
+        }
+        MediaType::Audio => {
+            if a.sample_rate != b.sample_rate {
+                return Some(format!(
+                    "sample rate differs ({} vs {})",
+                    a.sample_rate, b.sample_rate
+                ));
+            }
+
+            if a.ch_layout.nb_channels != b.ch_layout.nb_channels {
+                return Some(format!(
+                    "number of channels differs ({} vs {})",
+                    a.ch_layout.nb_channels, b.ch_layout.nb_channels
+                ));
+            }
+
+            let layout_a = ChannelLayout::copy_from_ptr(&a.ch_layout);
+            let layout_b = ChannelLayout::copy_from_ptr(&b.ch_layout);
+            if layout_a != layout_b {
+                return Some(format!(
+                    "channel layout differs ({} vs {})",
+                    layout_a, layout_b
+                ));
+            }
+
+            if a.format != b.format {
+                return Some("sample format differs".into());
+            }
+        }
+        _ => {}
+    }
+
+    // Codec headers such as SPS/PPS or AudioSpecificConfig, which the output only has once.
+    if extradata(a) != extradata(b) {
+        return Some("codec extradata differs".into());
+    }
+
+    None
+}
+
+unsafe fn extradata(par: &ffi::AVCodecParameters) -> &[u8] {
+    if par.extradata.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(par.extradata, par.extradata_size as _)
+    }
+}
@@ -5,9 +5,9 @@ use ffmpeg_sys_next::{av_dump_format, avformat_write_header};
 use super::dtor::{Destructor, InputDestructor, Mode, OutputDestructor};
 use crate::{
     error::Error,
-    ffi,
+    ffi, log,
     packet::Packet,
-    stream::{Stream, StreamIter, StreamIterMut, StreamMut},
+    stream::{IndexEntry, IndexFlags, Stream, StreamIter, StreamIterMut, StreamMut},
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -94,6 +94,15 @@ impl<D> Container<D> {
         self.url_cstr().and_then(|u| u.to_str().ok())
     }
 
+    /// Timestamp of the first frame, in `AV_TIME_BASE` units, if known.
+    #[inline]
+    pub fn start_time(&self) -> Option<i64> {
+        match unsafe { (*self.as_ptr()).start_time } {
+            ffi::AV_NOPTS_VALUE => None,
+            start_time => Some(start_time),
+        }
+    }
+
     #[inline]
     pub fn duration(&self) -> i64 {
         unsafe { (*self.as_ptr()).duration }
@@ -116,7 +125,8 @@ impl<D: Destructor> Container<D> {
         }
     }
 
-    pub fn dump(&mut self, index: i32) {
+    /// Prints detailed information about the container to the FFmpeg log.
+    pub fn dump(&self, index: i32) {
         let is_output = match Self::MODE {
             Mode::Input => 0,
             Mode::Output => 1,
@@ -125,15 +135,103 @@ impl<D: Destructor> Container<D> {
             .url_cstring()
             .unwrap_or_else(|| std::ffi::CString::new("").unwrap());
         unsafe {
-            av_dump_format(self.as_mut_ptr(), index, url.as_ptr(), is_output);
+            av_dump_format(self.as_ptr() as _, index, url.as_ptr(), is_output);
         }
     }
+
+    /// Same as [`Container::dump`], but returns the output instead of logging it.
+    pub fn dump_to_string(&self, index: i32) -> String {
+        let ((), output) = log::capture(|| self.dump(index));
+        output
+    }
+}
+
+impl<D: Destructor> std::fmt::Display for Container<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.dump_to_string(0))
+    }
 }
 
 impl InputContainer {
     pub fn demux(&mut self) -> PacketIter {
         PacketIter::new(self)
     }
+
+    /// Returns the keyframes of a stream, from the demuxer index if it has any,
+    /// or by scanning the whole input otherwise.
+    pub fn keyframes(&mut self, stream_index: u32) -> Result<Vec<IndexEntry>, Error> {
+        let keyframes: Vec<_> = {
+            let stream = self.stream(stream_index).ok_or(Error::StreamNotFound)?;
+            stream.index_entries().filter(|e| e.is_key()).collect()
+        };
+
+        if keyframes.is_empty() {
+            self.scan_keyframes(stream_index)
+        } else {
+            Ok(keyframes)
+        }
+    }
+
+    /// Reads every packet of the input and collects the keyframes of a stream.
+    ///
+    /// The input is rewound to its start afterwards, even if reading fails.
+    pub fn scan_keyframes(&mut self, stream_index: u32) -> Result<Vec<IndexEntry>, Error> {
+        if stream_index >= self.nb_streams() {
+            return Err(Error::StreamNotFound);
+        }
+
+        let keyframes = self.read_keyframes(stream_index);
+        let rewound = self.rewind();
+
+        let keyframes = keyframes?;
+        rewound?;
+        Ok(keyframes)
+    }
+
+    fn read_keyframes(&mut self, stream_index: u32) -> Result<Vec<IndexEntry>, Error> {
+        let mut keyframes = Vec::new();
+
+        loop {
+            let mut packet = Packet::empty();
+
+            match packet.read_from(self) {
+                Ok(()) => {}
+                Err(Error::Eof) => break,
+                Err(e) => return Err(e),
+            }
+
+            if packet.stream_index() != stream_index || !packet.is_key() {
+                continue;
+            }
+
+            if let Some(timestamp) = packet.pts().or(packet.dts()) {
+                keyframes.push(IndexEntry::new(
+                    packet.pos(),
+                    timestamp,
+                    IndexFlags::KEYFRAME,
+                    packet.size() as _,
+                ));
+            }
+        }
+
+        Ok(keyframes)
+    }
+
+    /// Seeks back to the first keyframe at or before the start of the input.
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        self.seek(self.start_time().unwrap_or(0))
+    }
+
+    /// Seeks to the first keyframe at or before `timestamp`, in `AV_TIME_BASE` units.
+    pub fn seek(&mut self, timestamp: i64) -> Result<(), Error> {
+        unsafe {
+            match ffi::avformat_seek_file(self.as_mut_ptr(), -1, i64::MIN, timestamp, timestamp, 0)
+            {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
 }
 
 pub struct PacketIter<'a>(&'a mut InputContainer);
@@ -187,6 +285,29 @@ impl OutputContainer {
         }
     }
 
+    /// Adds an empty stream, whose parameters and time base are to be set,
+    /// e.g. from an encoder.
+    pub fn add_stream(&mut self) -> StreamMut<OutputDestructor> {
+        unsafe {
+            let codec = std::ptr::null();
+            let ptr = ffi::avformat_new_stream(self.as_mut_ptr(), codec);
+
+            if ptr.is_null() {
+                // TODO: handle error
+                panic!("Failed to add stream");
+            }
+
+            let index = self.nb_streams() - 1;
+            StreamMut::wrap(self, index)
+        }
+    }
+
+    /// Whether the muxer wants codec headers in the extradata, see
+    /// [`Context::set_global_header`](crate::codec::Context::set_global_header).
+    pub fn needs_global_header(&self) -> bool {
+        unsafe { (*(*self.as_ptr()).oformat).flags & ffi::AVFMT_GLOBALHEADER != 0 }
+    }
+
     pub fn mux(&mut self, mut packet: Packet) -> Result<(), Error> {
         // let mut packet = packet;
 
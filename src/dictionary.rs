@@ -0,0 +1,179 @@
+use std::{
+    ffi::{CStr, CString},
+    marker::PhantomData,
+};
+
+use crate::{error::Error, ffi};
+
+/// An owned `AVDictionary`, used to pass options to FFmpeg and to hold metadata.
+pub struct Dictionary(*mut ffi::AVDictionary);
+
+unsafe impl Send for Dictionary {}
+
+impl Dictionary {
+    #[inline]
+    pub(crate) unsafe fn as_ptr(&self) -> *const ffi::AVDictionary {
+        self.0
+    }
+
+    #[inline]
+    pub(crate) unsafe fn as_mut_ptr(&mut self) -> *mut *mut ffi::AVDictionary {
+        &mut self.0
+    }
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Dictionary(std::ptr::null_mut())
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let key = CString::new(key).map_err(|_| Error::InvalidArgument)?;
+        let value = CString::new(value).map_err(|_| Error::InvalidArgument)?;
+
+        unsafe {
+            match ffi::av_dict_set(self.as_mut_ptr(), key.as_ptr(), value.as_ptr(), 0) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let key = CString::new(key).ok()?;
+
+        unsafe {
+            let entry = ffi::av_dict_get(self.as_ptr(), key.as_ptr(), std::ptr::null(), 0);
+
+            if entry.is_null() {
+                None
+            } else {
+                CStr::from_ptr((*entry).value).to_str().ok()
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        if let Ok(key) = CString::new(key) {
+            unsafe {
+                ffi::av_dict_set(self.as_mut_ptr(), key.as_ptr(), std::ptr::null(), 0);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        unsafe { ffi::av_dict_count(self.as_ptr()) as _ }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> DictionaryIter {
+        DictionaryIter {
+            ptr: self.0,
+            entry: std::ptr::null(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for Dictionary {
+    fn clone(&self) -> Self {
+        let mut dict = Dictionary::new();
+        dict.clone_from(self);
+        dict
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        unsafe {
+            ffi::av_dict_free(self.as_mut_ptr());
+            ffi::av_dict_copy(self.as_mut_ptr(), source.as_ptr(), 0);
+        }
+    }
+}
+
+impl Drop for Dictionary {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::av_dict_free(self.as_mut_ptr());
+        }
+    }
+}
+
+impl std::fmt::Debug for Dictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for Dictionary {
+    /// Entries with a key or value containing a NUL byte are skipped.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut dict = Dictionary::new();
+        for (key, value) in iter {
+            let _ = dict.set(key.as_ref(), value.as_ref());
+        }
+        dict
+    }
+}
+
+impl<'a> IntoIterator for &'a Dictionary {
+    type Item = (&'a str, &'a str);
+    type IntoIter = DictionaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct DictionaryIter<'a> {
+    ptr: *const ffi::AVDictionary,
+    entry: *const ffi::AVDictionaryEntry,
+    _marker: PhantomData<&'a Dictionary>,
+}
+
+impl<'a> Iterator for DictionaryIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            self.entry = ffi::av_dict_iterate(self.ptr, self.entry);
+
+            if self.entry.is_null() {
+                None
+            } else {
+                let key = CStr::from_ptr((*self.entry).key);
+                let value = CStr::from_ptr((*self.entry).value);
+                Some((
+                    key.to_str().unwrap_or_default(),
+                    value.to_str().unwrap_or_default(),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dictionary() {
+        let mut dict: Dictionary = [("threads", "4"), ("preset", "fast")].into_iter().collect();
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.get("preset"), Some("fast"));
+
+        dict.set("preset", "slow").unwrap();
+        dict.remove("threads");
+        assert_eq!(dict.iter().collect::<Vec<_>>(), vec![("preset", "slow")]);
+    }
+}
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::ffi;
+use crate::{codec::CodecId, ffi};
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -8,6 +8,24 @@ pub enum Error {
     InvalidPath,
     #[error("Write after close")]
     WriteAfterClose,
+    #[error("Invalid stream specifier: {0}")]
+    InvalidStreamSpecifier(String),
+    #[error("Codec {codec:?} is not supported by the {muxer} muxer")]
+    UnsupportedCodec { codec: CodecId, muxer: String },
+    #[error("Out of memory")]
+    OutOfMemory,
+    #[error("Invalid time range")]
+    InvalidRange,
+    #[error("No input given")]
+    NoInput,
+    #[error("Parser not found")]
+    ParserNotFound,
+    #[error("Unknown codec: {0}")]
+    UnknownCodec(String),
+    #[error("Codec {0:?} is not a text subtitle format")]
+    NotTextSubtitle(CodecId),
+    #[error("Input #{index} is incompatible with input #0: {reason}")]
+    IncompatibleInput { index: usize, reason: String },
     // Error codes from ffmpeg
     #[error("Bitstream filter not found")]
     BsfNotFound,
@@ -65,6 +83,12 @@ pub enum Error {
     HttpOther4xx,
     #[error("Server returned 5XX Server Error reply")]
     HttpServerError,
+    #[error("Resource temporarily unavailable")]
+    Again,
+    #[error("Invalid argument")]
+    InvalidArgument,
+    #[error("{}", strerror(*.0))]
+    Other(libc::c_int),
 }
 
 impl Error {
@@ -97,7 +121,20 @@ impl Error {
             ffi::AVERROR_HTTP_NOT_FOUND => Error::HttpNotFound,
             ffi::AVERROR_HTTP_OTHER_4XX => Error::HttpOther4xx,
             ffi::AVERROR_HTTP_SERVER_ERROR => Error::HttpServerError,
-            _ => unreachable!("Unknown error code: {}", code),
+            e if e == ffi::AVERROR(libc::ENOMEM) => Error::OutOfMemory,
+            e if e == ffi::AVERROR(libc::EAGAIN) => Error::Again,
+            e if e == ffi::AVERROR(libc::EINVAL) => Error::InvalidArgument,
+            e => Error::Other(e),
         }
     }
 }
+
+fn strerror(code: libc::c_int) -> String {
+    let mut buf = [0 as libc::c_char; ffi::AV_ERROR_MAX_STRING_SIZE];
+    unsafe {
+        ffi::av_strerror(code, buf.as_mut_ptr(), buf.len());
+        std::ffi::CStr::from_ptr(buf.as_ptr())
+            .to_string_lossy()
+            .into_owned()
+    }
+}
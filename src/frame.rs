@@ -1,4 +1,13 @@
-use crate::ffi;
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    channel::ChannelLayout,
+    color::{ChromaLocation, ColorPrimaries, ColorRange, ColorSpace, ColorTransferCharacteristic},
+    error::Error,
+    ffi,
+    sample::{Sample, SampleFormat},
+    Rational,
+};
 
 #[derive(PartialEq, Eq)]
 pub struct Frame(*mut ffi::AVFrame);
@@ -55,6 +64,18 @@ impl Frame {
         }
     }
 
+    #[inline(always)]
+    pub fn time_base(&self) -> Rational {
+        unsafe { (*self.as_ptr()).time_base.into() }
+    }
+
+    #[inline(always)]
+    pub fn set_time_base<R: Into<Rational>>(&mut self, time_base: R) {
+        unsafe {
+            (*self.as_mut_ptr()).time_base = time_base.into().into();
+        }
+    }
+
     #[inline(always)]
     pub fn timestamp(&self) -> Option<i64> {
         match unsafe { (*self.as_ptr()).best_effort_timestamp } {
@@ -72,6 +93,66 @@ impl Frame {
     pub fn flags(&self) -> Flags {
         Flags::from_bits_truncate(unsafe { (*self.as_ptr()).flags })
     }
+
+    /// Ensures the frame data is not shared with other frames, copying it if needed.
+    pub fn make_writable(&mut self) -> Result<(), Error> {
+        unsafe {
+            match ffi::av_frame_make_writable(self.as_mut_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    pub fn color_range(&self) -> ColorRange {
+        unsafe { (*self.as_ptr()).color_range.into() }
+    }
+
+    pub fn set_color_range(&mut self, color_range: ColorRange) {
+        unsafe {
+            (*self.as_mut_ptr()).color_range = color_range.into();
+        }
+    }
+
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        unsafe { (*self.as_ptr()).color_primaries.into() }
+    }
+
+    pub fn set_color_primaries(&mut self, color_primaries: ColorPrimaries) {
+        unsafe {
+            (*self.as_mut_ptr()).color_primaries = color_primaries.into();
+        }
+    }
+
+    pub fn color_transfer_characteristic(&self) -> ColorTransferCharacteristic {
+        unsafe { (*self.as_ptr()).color_trc.into() }
+    }
+
+    pub fn set_color_transfer_characteristic(&mut self, transfer: ColorTransferCharacteristic) {
+        unsafe {
+            (*self.as_mut_ptr()).color_trc = transfer.into();
+        }
+    }
+
+    pub fn color_space(&self) -> ColorSpace {
+        unsafe { (*self.as_ptr()).colorspace.into() }
+    }
+
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        unsafe {
+            (*self.as_mut_ptr()).colorspace = color_space.into();
+        }
+    }
+
+    pub fn chroma_location(&self) -> ChromaLocation {
+        unsafe { (*self.as_ptr()).chroma_location.into() }
+    }
+
+    pub fn set_chroma_location(&mut self, chroma_location: ChromaLocation) {
+        unsafe {
+            (*self.as_mut_ptr()).chroma_location = chroma_location.into();
+        }
+    }
 }
 
 impl Drop for Frame {
@@ -96,6 +177,187 @@ impl VideoFrame {
     // }
 }
 
+/// A frame of audio samples.
+#[derive(PartialEq, Eq)]
+pub struct AudioFrame(Frame);
+
+impl AudioFrame {
+    #[inline(always)]
+    pub fn empty() -> Self {
+        Self(Frame::empty())
+    }
+
+    /// Allocates a frame of `nb_samples` samples per channel.
+    pub fn new(
+        format: SampleFormat,
+        layout: &ChannelLayout,
+        nb_samples: usize,
+    ) -> Result<Self, Error> {
+        let mut frame = Self::empty();
+
+        unsafe {
+            let ptr = frame.as_mut_ptr();
+            (*ptr).format = ffi::AVSampleFormat::from(format) as _;
+            (*ptr).nb_samples = nb_samples as _;
+
+            layout.copy_to_ptr(&mut (*ptr).ch_layout)?;
+
+            match ffi::av_frame_get_buffer(ptr, 0) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(frame),
+            }
+        }
+    }
+
+    /// Number of samples per channel.
+    #[inline(always)]
+    pub fn nb_samples(&self) -> usize {
+        unsafe { (*self.as_ptr()).nb_samples as _ }
+    }
+
+    #[inline(always)]
+    pub fn sample_rate(&self) -> u32 {
+        unsafe { (*self.as_ptr()).sample_rate as _ }
+    }
+
+    #[inline(always)]
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_rate = sample_rate as _;
+        }
+    }
+
+    #[inline(always)]
+    pub fn format(&self) -> SampleFormat {
+        unsafe { SampleFormat::from((*self.as_ptr()).format) }
+    }
+
+    pub fn channel_layout(&self) -> ChannelLayout {
+        unsafe { ChannelLayout::copy_from_ptr(&(*self.as_ptr()).ch_layout) }
+    }
+
+    pub fn set_channel_layout(&mut self, layout: &ChannelLayout) -> Result<(), Error> {
+        unsafe { layout.copy_to_ptr(&mut (*self.as_mut_ptr()).ch_layout) }
+    }
+
+    #[inline(always)]
+    pub fn nb_channels(&self) -> usize {
+        unsafe { (*self.as_ptr()).ch_layout.nb_channels as _ }
+    }
+
+    #[inline(always)]
+    pub fn is_planar(&self) -> bool {
+        self.format().is_planar()
+    }
+
+    /// One plane per channel for planar formats, a single interleaved one otherwise.
+    #[inline(always)]
+    pub fn nb_planes(&self) -> usize {
+        if self.is_planar() {
+            self.nb_channels()
+        } else {
+            1
+        }
+    }
+
+    /// Samples of a plane, i.e. of one channel for planar formats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` does not match the sample format, or if `index` is out of bounds.
+    pub fn plane<T: Sample>(&self, index: usize) -> &[T] {
+        let len = self.plane_len::<T>(index);
+
+        unsafe {
+            let data = *(*self.as_ptr()).extended_data.add(index);
+            std::slice::from_raw_parts(data as *const T, len)
+        }
+    }
+
+    /// Mutable samples of a plane, see [`AudioFrame::plane`].
+    ///
+    /// The frame is made writable first, copying its buffers if they are shared with
+    /// another frame, e.g. one output by a decoder or cloned.
+    pub fn plane_mut<T: Sample>(&mut self, index: usize) -> Result<&mut [T], Error> {
+        let len = self.plane_len::<T>(index);
+        self.make_writable()?;
+
+        unsafe {
+            let data = *(*self.as_mut_ptr()).extended_data.add(index);
+            Ok(std::slice::from_raw_parts_mut(data as *mut T, len))
+        }
+    }
+
+    /// Interleaved samples of all channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the format is planar, or if `T` does not match the sample format.
+    pub fn data<T: Sample>(&self) -> &[T] {
+        assert!(!self.is_planar(), "planar audio frame, use `plane` instead");
+        self.plane(0)
+    }
+
+    /// Mutable interleaved samples of all channels, see [`AudioFrame::data`] and
+    /// [`AudioFrame::plane_mut`].
+    pub fn data_mut<T: Sample>(&mut self) -> Result<&mut [T], Error> {
+        assert!(
+            !self.is_planar(),
+            "planar audio frame, use `plane_mut` instead"
+        );
+        self.plane_mut(0)
+    }
+
+    fn plane_len<T: Sample>(&self, index: usize) -> usize {
+        let format = self.format();
+        assert!(
+            format.packed() == T::FORMAT && format != SampleFormat::None,
+            "cannot access {:?} samples as {:?}",
+            format,
+            T::FORMAT
+        );
+        assert!(
+            index < self.nb_planes(),
+            "plane index {} out of bounds ({} planes)",
+            index,
+            self.nb_planes()
+        );
+        assert!(!self.is_empty(), "audio frame has no data");
+
+        if self.is_planar() {
+            self.nb_samples()
+        } else {
+            self.nb_samples() * self.nb_channels()
+        }
+    }
+}
+
+impl Deref for AudioFrame {
+    type Target = Frame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for AudioFrame {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Frame> for AudioFrame {
+    fn from(frame: Frame) -> Self {
+        Self(frame)
+    }
+}
+
+impl From<AudioFrame> for Frame {
+    fn from(frame: AudioFrame) -> Self {
+        frame.0
+    }
+}
+
 bitflags! {
     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
     pub struct Flags: i32 {
@@ -5,17 +5,30 @@ extern crate bitflags;
 
 pub use ffmpeg_sys_next as ffi;
 
+pub mod bsf;
+pub mod channel;
 pub mod codec;
+pub mod color;
 pub mod container;
+pub mod dictionary;
 pub mod error;
 pub mod frame;
 pub mod frame2;
+pub mod option;
 pub mod packet;
 pub mod pixel;
+pub mod sample;
 pub mod stream;
+pub mod subtitle;
 
+mod concat;
 mod io;
+mod log;
+mod remux;
+pub use concat::Concat;
 pub use io::{create, open};
+pub use log::{set_log_callback, LogCallback};
+pub use remux::{cut, remux, RemuxOptions, StreamSelector};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum MediaType {
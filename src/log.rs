@@ -0,0 +1,183 @@
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, c_void},
+    sync::Mutex,
+};
+
+use crate::ffi;
+
+#[cfg(all(target_arch = "x86_64", not(windows)))]
+pub type VaList = *mut ffi::__va_list_tag;
+
+#[cfg(not(all(target_arch = "x86_64", not(windows))))]
+pub type VaList = ffi::va_list;
+
+/// FFmpeg log callback, see [`set_log_callback`].
+pub type LogCallback = unsafe extern "C" fn(*mut c_void, c_int, *const c_char, VaList);
+
+/// Serializes capture sessions, since the FFmpeg log callback is process-wide.
+static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Callback set with [`set_log_callback`], as FFmpeg has no getter for the current one.
+static USER_CALLBACK: Mutex<Option<LogCallback>> = Mutex::new(None);
+
+thread_local! {
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+}
+
+struct Capture {
+    buf: String,
+    print_prefix: c_int,
+}
+
+unsafe extern "C" fn capture_callback(
+    avcl: *mut c_void,
+    level: c_int,
+    fmt: *const c_char,
+    vl: VaList,
+) {
+    let captured = CAPTURE.with(|capture| {
+        let mut capture = capture.borrow_mut();
+        let capture = match capture.as_mut() {
+            Some(capture) => capture,
+            None => return false,
+        };
+
+        if level <= ffi::AV_LOG_INFO {
+            // `vl` can only be consumed once, so longer lines are truncated like
+            // they are by the default callback.
+            let mut line = [0 as c_char; 1024];
+            let len = ffi::av_log_format_line2(
+                avcl,
+                level,
+                fmt,
+                vl,
+                line.as_mut_ptr(),
+                line.len() as _,
+                &mut capture.print_prefix,
+            );
+
+            if len > 0 {
+                let line = std::ffi::CStr::from_ptr(line.as_ptr());
+                capture.buf.push_str(&line.to_string_lossy());
+            }
+        }
+
+        true
+    });
+
+    if !captured {
+        user_callback()(avcl, level, fmt, vl);
+    }
+}
+
+/// Sets the FFmpeg log callback, `None` reinstalling the default one.
+///
+/// Use this instead of `av_log_set_callback`, so that the callback is reinstalled
+/// after the crate temporarily captures log output, e.g. to format a container.
+pub fn set_log_callback(callback: Option<LogCallback>) {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    *USER_CALLBACK.lock().unwrap_or_else(|e| e.into_inner()) = callback;
+
+    unsafe {
+        ffi::av_log_set_callback(Some(user_callback()));
+    }
+}
+
+fn user_callback() -> LogCallback {
+    USER_CALLBACK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .unwrap_or(ffi::av_log_default_callback)
+}
+
+/// Runs `f` with FFmpeg log output of the current thread redirected into a string.
+///
+/// Messages logged by other threads while `f` runs are forwarded to the callback set
+/// with [`set_log_callback`], which is reinstalled afterwards. Captures can be nested,
+/// the inner one taking the output of `f` until it returns.
+pub(crate) fn capture<F, R>(f: F) -> (R, String)
+where
+    F: FnOnce() -> R,
+{
+    let nested = CAPTURE.with(|capture| capture.borrow().is_some());
+    // Already held by the outer capture of this thread.
+    let _guard = (!nested).then(|| CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner()));
+
+    let outer = CAPTURE.with(|capture| {
+        capture.replace(Some(Capture {
+            buf: String::new(),
+            print_prefix: 1,
+        }))
+    });
+
+    if !nested {
+        unsafe {
+            ffi::av_log_set_callback(Some(capture_callback));
+        }
+    }
+
+    let restore = Restore { outer };
+    let ret = f();
+    let buf = CAPTURE
+        .with(|capture| capture.borrow_mut().take())
+        .map(|capture| capture.buf)
+        .unwrap_or_default();
+    drop(restore);
+
+    (ret, buf)
+}
+
+/// Restores the outer capture, or reinstalls the user callback, even if the captured
+/// closure panics.
+struct Restore {
+    outer: Option<Capture>,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let outer = self.outer.take();
+        let nested = outer.is_some();
+        CAPTURE.with(|capture| *capture.borrow_mut() = outer);
+
+        if !nested {
+            unsafe {
+                ffi::av_log_set_callback(Some(user_callback()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let ((), output) = capture(|| unsafe {
+            ffi::av_log(
+                std::ptr::null_mut(),
+                ffi::AV_LOG_INFO,
+                c"answer: %d\n".as_ptr(),
+                42 as c_int,
+            );
+        });
+        assert_eq!(output, "answer: 42\n");
+    }
+
+    #[test]
+    fn test_nested_capture() {
+        let log = |msg: &std::ffi::CStr| unsafe {
+            ffi::av_log(std::ptr::null_mut(), ffi::AV_LOG_INFO, msg.as_ptr());
+        };
+
+        let (((), inner), outer) = capture(|| {
+            log(c"outer\n");
+            let ret = capture(|| log(c"inner\n"));
+            log(c"outer again\n");
+            ret
+        });
+        assert_eq!(inner, "inner\n");
+        assert_eq!(outer, "outer\nouter again\n");
+    }
+}
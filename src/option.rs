@@ -0,0 +1,264 @@
+use std::ffi::{c_void, CStr, CString};
+
+use crate::{
+    bsf::BitstreamFilter, codec::Context, container::Container, error::Error, ffi,
+    pixel::PixelFormat, sample::SampleFormat, Rational,
+};
+
+/// Access to the AVOptions of an FFmpeg object, such as the `preset` and `crf` of libx264,
+/// the `movflags` of the mov muxer or the `fflags` of a demuxer.
+///
+/// Options are looked up on the object, then on its children, e.g. the private context of
+/// its codec or format. Unknown options return [`Error::OptionNotFound`].
+///
+/// The typed format setters are called [`set_option_pixel_format`] and
+/// [`set_option_sample_format`] so that the inherent `set_pixel_format` and
+/// `set_sample_format` of [`Context`] do not shadow them.
+///
+/// [`set_option_pixel_format`]: Options::set_option_pixel_format
+/// [`set_option_sample_format`]: Options::set_option_sample_format
+pub trait Options: private::AsOptions {
+    /// Sets an option from its string representation, as on the command line of `ffmpeg`.
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        let name = c_string(name)?;
+        let value = c_string(value)?;
+        unsafe {
+            check(ffi::av_opt_set(
+                self.as_mut_options_ptr(),
+                name.as_ptr(),
+                value.as_ptr(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            ))
+        }
+    }
+
+    /// Sets an integer option, including flags and durations.
+    fn set_int(&mut self, name: &str, value: i64) -> Result<(), Error> {
+        let name = c_string(name)?;
+        unsafe {
+            check(ffi::av_opt_set_int(
+                self.as_mut_options_ptr(),
+                name.as_ptr(),
+                value,
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            ))
+        }
+    }
+
+    /// Sets a floating point option.
+    fn set_double(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        let name = c_string(name)?;
+        unsafe {
+            check(ffi::av_opt_set_double(
+                self.as_mut_options_ptr(),
+                name.as_ptr(),
+                value,
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            ))
+        }
+    }
+
+    /// Sets a rational option, such as a frame rate or an aspect ratio.
+    fn set_rational<R: Into<Rational>>(&mut self, name: &str, value: R) -> Result<(), Error> {
+        let name = c_string(name)?;
+        unsafe {
+            check(ffi::av_opt_set_q(
+                self.as_mut_options_ptr(),
+                name.as_ptr(),
+                value.into().into(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            ))
+        }
+    }
+
+    /// Sets an option holding a pixel format, such as the `pixel_format` of a demuxer.
+    fn set_option_pixel_format(&mut self, name: &str, format: PixelFormat) -> Result<(), Error> {
+        let name = c_string(name)?;
+        unsafe {
+            check(ffi::av_opt_set_pixel_fmt(
+                self.as_mut_options_ptr(),
+                name.as_ptr(),
+                format.into(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            ))
+        }
+    }
+
+    /// Sets an option holding a sample format, such as the `out_sample_fmt` of a resampler.
+    fn set_option_sample_format(&mut self, name: &str, format: SampleFormat) -> Result<(), Error> {
+        let name = c_string(name)?;
+        unsafe {
+            check(ffi::av_opt_set_sample_fmt(
+                self.as_mut_options_ptr(),
+                name.as_ptr(),
+                format.into(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            ))
+        }
+    }
+
+    /// Returns the string representation of an option.
+    fn get_option(&self, name: &str) -> Result<String, Error> {
+        let name = c_string(name)?;
+        unsafe {
+            let mut value = std::ptr::null_mut();
+            check(ffi::av_opt_get(
+                self.as_options_ptr(),
+                name.as_ptr(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+                &mut value,
+            ))?;
+
+            let s = CStr::from_ptr(value as *const _)
+                .to_string_lossy()
+                .into_owned();
+            ffi::av_free(value as *mut _);
+            Ok(s)
+        }
+    }
+
+    /// Returns the value of an integer option, including flags and durations.
+    fn get_int(&self, name: &str) -> Result<i64, Error> {
+        let name = c_string(name)?;
+        let mut value = 0;
+        unsafe {
+            check(ffi::av_opt_get_int(
+                self.as_options_ptr(),
+                name.as_ptr(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+                &mut value,
+            ))?;
+        }
+        Ok(value)
+    }
+
+    /// Returns the value of a floating point option.
+    fn get_double(&self, name: &str) -> Result<f64, Error> {
+        let name = c_string(name)?;
+        let mut value = 0.0;
+        unsafe {
+            check(ffi::av_opt_get_double(
+                self.as_options_ptr(),
+                name.as_ptr(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+                &mut value,
+            ))?;
+        }
+        Ok(value)
+    }
+
+    /// Returns the value of a rational option.
+    fn get_rational(&self, name: &str) -> Result<Rational, Error> {
+        let name = c_string(name)?;
+        let mut value = ffi::AVRational { num: 0, den: 1 };
+        unsafe {
+            check(ffi::av_opt_get_q(
+                self.as_options_ptr(),
+                name.as_ptr(),
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+                &mut value,
+            ))?;
+        }
+        Ok(value.into())
+    }
+
+    /// Returns whether the object or one of its children has an option called `name`.
+    fn has_option(&self, name: &str) -> bool {
+        let Ok(name) = CString::new(name) else {
+            return false;
+        };
+        unsafe {
+            !ffi::av_opt_find(
+                self.as_options_ptr(),
+                name.as_ptr(),
+                std::ptr::null(),
+                0,
+                ffi::AV_OPT_SEARCH_CHILDREN as _,
+            )
+            .is_null()
+        }
+    }
+}
+
+fn c_string(s: &str) -> Result<CString, Error> {
+    CString::new(s).map_err(|_| Error::InvalidArgument)
+}
+
+fn check(ret: libc::c_int) -> Result<(), Error> {
+    match ret {
+        e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+        _ => Ok(()),
+    }
+}
+
+impl private::AsOptions for Context {
+    unsafe fn as_options_ptr(&self) -> *mut c_void {
+        self.as_ptr() as *mut _
+    }
+
+    unsafe fn as_mut_options_ptr(&mut self) -> *mut c_void {
+        self.as_mut_ptr() as *mut _
+    }
+}
+
+impl Options for Context {}
+
+impl<D> private::AsOptions for Container<D> {
+    unsafe fn as_options_ptr(&self) -> *mut c_void {
+        self.as_ptr() as *mut _
+    }
+
+    unsafe fn as_mut_options_ptr(&mut self) -> *mut c_void {
+        self.as_mut_ptr() as *mut _
+    }
+}
+
+impl<D> Options for Container<D> {}
+
+impl private::AsOptions for BitstreamFilter {
+    unsafe fn as_options_ptr(&self) -> *mut c_void {
+        self.as_ptr() as *mut _
+    }
+
+    unsafe fn as_mut_options_ptr(&mut self) -> *mut c_void {
+        self.as_mut_ptr() as *mut _
+    }
+}
+
+impl Options for BitstreamFilter {}
+
+mod private {
+    use std::ffi::c_void;
+
+    /// Pointer to a struct whose first member is a pointer to an `AVClass`.
+    pub trait AsOptions {
+        /// Only for reading options, FFmpeg takes a mutable pointer regardless.
+        unsafe fn as_options_ptr(&self) -> *mut c_void;
+
+        unsafe fn as_mut_options_ptr(&mut self) -> *mut c_void;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::CodecFlags;
+
+    #[test]
+    fn test_codec_options() {
+        let mut ctx = Context::new();
+
+        ctx.set_int("b", 2_000_000).unwrap();
+        assert_eq!(ctx.bit_rate(), 2_000_000);
+        assert_eq!(ctx.get_option("b").unwrap(), "2000000");
+
+        ctx.set_option("flags", "+global_header").unwrap();
+        assert!(ctx.flags().contains(CodecFlags::GLOBAL_HEADER));
+
+        assert!(ctx.has_option("threads"));
+        assert!(matches!(
+            ctx.set_option("no_such_option", "1"),
+            Err(Error::OptionNotFound)
+        ));
+    }
+}
@@ -21,14 +21,29 @@ impl Packet {
         }
     }
 
+    /// Allocates a packet with `size` bytes of data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocation fails, see [`Packet::try_new`].
     #[inline]
     pub fn new(size: usize) -> Self {
+        match Packet::try_new(size) {
+            Ok(pkt) => pkt,
+            Err(e) => panic!("Failed to allocate packet of {} bytes: {}", size, e),
+        }
+    }
+
+    #[inline]
+    pub fn try_new(size: usize) -> Result<Self, Error> {
+        let size = libc::c_int::try_from(size).map_err(|_| Error::InvalidArgument)?;
+        let mut pkt = Packet::empty();
+
         unsafe {
-            let mut pkt: ffi::AVPacket = std::mem::zeroed();
-            ffi::av_init_packet(&mut pkt);
-            // TODO: handle error
-            ffi::av_new_packet(&mut pkt, size as _);
-            Packet(pkt)
+            match ffi::av_new_packet(pkt.as_mut_ptr(), size) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(pkt),
+            }
         }
     }
 
@@ -39,6 +54,35 @@ impl Packet {
         pkt
     }
 
+    /// Wraps `data` into a packet, taking ownership of its buffer.
+    ///
+    /// FFmpeg requires `AV_INPUT_BUFFER_PADDING_SIZE` zeroed bytes after the packet data,
+    /// so the data is only left in place if the vector has enough spare capacity for them,
+    /// and is copied by the reallocation of the vector otherwise.
+    pub fn from_vec(mut data: Vec<u8>) -> Result<Self, Error> {
+        let size = data.len();
+        let c_size = libc::c_int::try_from(size).map_err(|_| Error::InvalidArgument)?;
+        data.resize(size + PADDING_SIZE, 0);
+
+        unsafe {
+            let ptr = data.as_mut_ptr();
+            let opaque = Box::into_raw(Box::new(data));
+            let buf =
+                ffi::av_buffer_create(ptr, size + PADDING_SIZE, Some(free_vec), opaque as _, 0);
+
+            if buf.is_null() {
+                drop(Box::from_raw(opaque));
+                return Err(Error::OutOfMemory);
+            }
+
+            let mut pkt = Packet::empty();
+            pkt.0.buf = buf;
+            pkt.0.data = (*buf).data;
+            pkt.0.size = c_size;
+            Ok(pkt)
+        }
+    }
+
     #[inline]
     pub fn shrink(&mut self, size: usize) {
         unsafe {
@@ -46,10 +90,69 @@ impl Packet {
         }
     }
 
+    /// Grows the packet data by `size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reallocation fails, see [`Packet::try_grow`].
     #[inline]
     pub fn grow(&mut self, size: usize) {
+        if let Err(e) = self.try_grow(size) {
+            panic!("Failed to grow packet by {} bytes: {}", size, e);
+        }
+    }
+
+    #[inline]
+    pub fn try_grow(&mut self, size: usize) -> Result<(), Error> {
+        let size = libc::c_int::try_from(size).map_err(|_| Error::InvalidArgument)?;
+
         unsafe {
-            ffi::av_grow_packet(&mut self.0, size as _);
+            match ffi::av_grow_packet(&mut self.0, size) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Returns a new packet with its own copy of the data.
+    #[inline]
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        let mut pkt = Packet::empty();
+        pkt.try_clone_from(self)?;
+        Ok(pkt)
+    }
+
+    #[inline]
+    fn try_clone_from(&mut self, source: &Self) -> Result<(), Error> {
+        unsafe {
+            ffi::av_packet_unref(&mut self.0);
+
+            match ffi::av_packet_ref(&mut self.0, &source.0) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => self.make_writable(),
+            }
+        }
+    }
+
+    /// Ensures the packet data is not shared with other packets, copying it if needed.
+    #[inline]
+    pub fn make_writable(&mut self) -> Result<(), Error> {
+        unsafe {
+            match ffi::av_packet_make_writable(&mut self.0) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Copies the properties (timestamps, flags, side data, ...) of `source`, but not its data.
+    #[inline]
+    pub fn copy_props(&mut self, source: &Packet) -> Result<(), Error> {
+        unsafe {
+            match ffi::av_packet_copy_props(&mut self.0, &source.0) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(()),
+            }
         }
     }
 
@@ -252,6 +355,46 @@ impl Packet {
     }
 }
 
+#[cfg(feature = "bytes")]
+impl Packet {
+    /// Wraps `data` into a packet, see [`Packet::from_vec`]. The data is copied if it
+    /// does not uniquely own its underlying vector.
+    pub fn from_bytes(data: bytes::Bytes) -> Result<Self, Error> {
+        Packet::from_vec(Vec::from(data))
+    }
+
+    /// Converts the packet into its data, without copying it.
+    pub fn into_bytes(self) -> bytes::Bytes {
+        if self.data().is_none() {
+            return bytes::Bytes::new();
+        }
+
+        bytes::Bytes::from_owner(SharedData(self))
+    }
+
+    /// Returns a new reference to the packet data, without copying it.
+    pub fn data_shared(&self) -> Result<bytes::Bytes, Error> {
+        let mut pkt = Packet::empty();
+
+        unsafe {
+            match ffi::av_packet_ref(pkt.as_mut_ptr(), self.as_ptr()) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                _ => Ok(pkt.into_bytes()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+struct SharedData(Packet);
+
+#[cfg(feature = "bytes")]
+impl AsRef<[u8]> for SharedData {
+    fn as_ref(&self) -> &[u8] {
+        self.0.data().unwrap_or(&[])
+    }
+}
+
 impl Clone for Packet {
     #[inline]
     fn clone(&self) -> Self {
@@ -262,9 +405,8 @@ impl Clone for Packet {
 
     #[inline]
     fn clone_from(&mut self, source: &Self) {
-        unsafe {
-            ffi::av_packet_ref(&mut self.0, &source.0);
-            ffi::av_packet_make_writable(&mut self.0);
+        if let Err(e) = self.try_clone_from(source) {
+            panic!("Failed to clone packet: {}", e);
         }
     }
 }
@@ -278,6 +420,12 @@ impl Drop for Packet {
     }
 }
 
+const PADDING_SIZE: usize = ffi::AV_INPUT_BUFFER_PADDING_SIZE as usize;
+
+unsafe extern "C" fn free_vec(opaque: *mut libc::c_void, _data: *mut u8) {
+    drop(Box::from_raw(opaque as *mut Vec<u8>));
+}
+
 bitflags! {
     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
     pub struct Flags: i32 {
@@ -288,3 +436,28 @@ bitflags! {
         const DISPOSABLE = ffi::AV_PKT_FLAG_DISPOSABLE;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_vec() {
+        let data = vec![1u8, 2, 3, 4];
+        let pkt = Packet::from_vec(data).unwrap();
+        assert_eq!(pkt.size(), 4);
+        assert_eq!(pkt.data(), Some(&[1u8, 2, 3, 4][..]));
+
+        let cloned = pkt.clone();
+        drop(pkt);
+        assert_eq!(cloned.data(), Some(&[1u8, 2, 3, 4][..]));
+    }
+
+    #[test]
+    fn test_try_grow() {
+        let mut pkt = Packet::try_new(4).unwrap();
+        pkt.try_grow(4).unwrap();
+        assert_eq!(pkt.size(), 8);
+        assert!(pkt.try_grow(usize::MAX).is_err());
+    }
+}
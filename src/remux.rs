@@ -0,0 +1,353 @@
+use crate::{
+    codec::CodecId,
+    container::{Container, InputContainer, OutputContainer},
+    error::Error,
+    ffi,
+    packet::Packet,
+    stream::Stream,
+    MediaType,
+};
+
+/// Selects input streams to be copied by [`remux`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamSelector {
+    /// All streams of the given media type.
+    Type(MediaType),
+    /// The stream with the given index.
+    Index(u32),
+    /// Streams matching an FFmpeg stream specifier, e.g. `v:0` or `a`.
+    Specifier(String),
+}
+
+impl StreamSelector {
+    pub fn matches<D>(&self, stream: &Stream<D>) -> Result<bool, Error> {
+        match self {
+            StreamSelector::Type(kind) => Ok(stream.parameters().codec_type() == *kind),
+            StreamSelector::Index(index) => Ok(stream.index() == *index),
+            StreamSelector::Specifier(spec) => stream.matches_specifier(spec),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RemuxOptions {
+    streams: Vec<StreamSelector>,
+    copy_metadata: bool,
+    copy_chapters: bool,
+}
+
+impl RemuxOptions {
+    pub fn new() -> Self {
+        RemuxOptions {
+            streams: Vec::new(),
+            copy_metadata: true,
+            copy_chapters: true,
+        }
+    }
+
+    /// Adds a stream selector. If none is given, all streams are copied.
+    pub fn stream(mut self, selector: StreamSelector) -> Self {
+        self.streams.push(selector);
+        self
+    }
+
+    /// Copies container and stream metadata. Enabled by default.
+    pub fn copy_metadata(mut self, copy_metadata: bool) -> Self {
+        self.copy_metadata = copy_metadata;
+        self
+    }
+
+    /// Copies chapters. Enabled by default.
+    pub fn copy_chapters(mut self, copy_chapters: bool) -> Self {
+        self.copy_chapters = copy_chapters;
+        self
+    }
+
+    fn selects<D>(&self, stream: &Stream<D>) -> Result<bool, Error> {
+        if self.streams.is_empty() {
+            return Ok(true);
+        }
+
+        for selector in &self.streams {
+            if selector.matches(stream)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl Default for RemuxOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Copies the selected streams of `input` into `output` without re-encoding,
+/// and writes the trailer of `output`.
+pub fn remux(
+    input: &mut InputContainer,
+    output: &mut OutputContainer,
+    options: RemuxOptions,
+) -> Result<(), Error> {
+    let mapping = map_streams(input, output, &options)?;
+
+    loop {
+        let mut packet = Packet::empty();
+
+        match packet.read_from(input) {
+            Ok(()) => {}
+            Err(Error::Eof) => break,
+            Err(e) => return Err(e),
+        }
+
+        let ist_index = packet.stream_index();
+        let ost_index = match mapping.get(ist_index as usize) {
+            Some(Some(index)) => *index,
+            _ => continue,
+        };
+
+        if let Some(ist) = input.stream(ist_index) {
+            packet.set_time_base(ist.time_base());
+        }
+        packet.set_stream_index(ost_index);
+        packet.set_pos(-1);
+
+        output.mux(packet)?;
+    }
+
+    output.write_trailer()
+}
+
+/// Copies the packets of `input` between `start` and `end` into `output` without
+/// re-encoding, and writes the trailer of `output`.
+///
+/// `start` and `end` are absolute timestamps in `AV_TIME_BASE` units, as used by
+/// [`InputContainer::seek`], not durations from the start of the input: add
+/// [`Container::start_time`] to cut relative to it. Copying starts at the keyframe at or
+/// before `start`, and timestamps of all streams are rebased by the same offset so that
+/// the output starts at zero. If the muxer accepts negative timestamps, `start` itself is
+/// mapped to zero and the packets before it are kept, so that the muxer can hide them
+/// with an edit list.
+///
+/// Packets are copied until their decoding timestamp reaches `end`, so that the frames
+/// displayed before `end` keep their references. Copying stops once every audio and video
+/// stream has reached `end`, without waiting for sparse streams such as subtitles.
+pub fn cut(
+    input: &mut InputContainer,
+    output: &mut OutputContainer,
+    start: i64,
+    end: i64,
+) -> Result<(), Error> {
+    if end <= start {
+        return Err(Error::InvalidRange);
+    }
+
+    let options = RemuxOptions::new().copy_chapters(false);
+    let mapping = map_streams(input, output, &options)?;
+
+    input.seek(start)?;
+
+    let edit_list = unsafe { (*(*output.as_ptr()).oformat).flags & ffi::AVFMT_TS_NEGATIVE != 0 };
+    let mut offset = if edit_list { Some(start) } else { None };
+
+    // Streams that decide when to stop, all copied streams if there are only sparse ones.
+    let mut pending: Vec<bool> = input
+        .streams()
+        .map(|ist| {
+            mapping[ist.index() as usize].is_some()
+                && matches!(
+                    ist.parameters().codec_type(),
+                    MediaType::Video | MediaType::Audio
+                )
+        })
+        .collect();
+    if !pending.contains(&true) {
+        pending = mapping.iter().map(Option::is_some).collect();
+    }
+    let mut done = vec![false; mapping.len()];
+
+    loop {
+        let mut packet = Packet::empty();
+
+        match packet.read_from(input) {
+            Ok(()) => {}
+            Err(Error::Eof) => break,
+            Err(e) => return Err(e),
+        }
+
+        let ist_index = packet.stream_index();
+        let ost_index = match mapping.get(ist_index as usize) {
+            Some(Some(index)) if !done[ist_index as usize] => *index,
+            _ => continue,
+        };
+
+        let time_base = match input.stream(ist_index) {
+            Some(ist) => ist.time_base(),
+            None => continue,
+        };
+        let to_av_time_base =
+            |ts: i64| unsafe { ffi::av_rescale_q(ts, time_base.into(), ffi::AV_TIME_BASE_Q) };
+
+        if packet
+            .dts()
+            .or(packet.pts())
+            .is_some_and(|ts| to_av_time_base(ts) >= end)
+        {
+            done[ist_index as usize] = true;
+            pending[ist_index as usize] = false;
+
+            if !pending.contains(&true) {
+                break;
+            }
+            continue;
+        }
+
+        let offset = *offset
+            .get_or_insert_with(|| packet.dts().or(packet.pts()).map_or(start, to_av_time_base));
+        let offset = unsafe { ffi::av_rescale_q(offset, ffi::AV_TIME_BASE_Q, time_base.into()) };
+
+        packet.set_pts(packet.pts().map(|pts| pts - offset));
+        packet.set_dts(packet.dts().map(|dts| dts - offset));
+        packet.set_time_base(time_base);
+        packet.set_stream_index(ost_index);
+        packet.set_pos(-1);
+
+        output.mux(packet)?;
+    }
+
+    output.write_trailer()
+}
+
+/// Adds an output stream for every input stream selected by `options`, copies metadata
+/// and chapters, and returns the output stream index of every input stream.
+pub(crate) fn map_streams(
+    input: &InputContainer,
+    output: &mut OutputContainer,
+    options: &RemuxOptions,
+) -> Result<Vec<Option<u32>>, Error> {
+    let mut mapping = vec![None; input.nb_streams() as usize];
+
+    for ist in input.streams() {
+        if !options.selects(&ist)? {
+            continue;
+        }
+
+        check_codec_supported(output, ist.parameters().codec_id())?;
+
+        let mut ost = output.add_stream_like(&ist);
+        ost.set_time_base(ist.time_base());
+
+        unsafe {
+            let ost_ptr = ost.as_mut_ptr();
+            (*(*ost_ptr).codecpar).codec_tag = 0;
+
+            if options.copy_metadata {
+                copy_dict(&mut (*ost_ptr).metadata, (*ist.as_ptr()).metadata)?;
+            }
+        }
+
+        mapping[ist.index() as usize] = Some(ost.index());
+    }
+
+    if mapping.iter().all(Option::is_none) {
+        return Err(Error::StreamNotFound);
+    }
+
+    unsafe {
+        if options.copy_metadata {
+            copy_dict(
+                &mut (*output.as_mut_ptr()).metadata,
+                (*input.as_ptr()).metadata,
+            )?;
+        }
+
+        if options.copy_chapters {
+            copy_chapters(input, output, options.copy_metadata)?;
+        }
+    }
+
+    Ok(mapping)
+}
+
+pub(crate) fn check_codec_supported(
+    output: &OutputContainer,
+    codec_id: CodecId,
+) -> Result<(), Error> {
+    if codec_id == CodecId::None {
+        return Ok(());
+    }
+
+    unsafe {
+        let oformat = (*output.as_ptr()).oformat;
+
+        if ffi::avformat_query_codec(oformat, codec_id.into(), ffi::FF_COMPLIANCE_NORMAL) == 0 {
+            let muxer = std::ffi::CStr::from_ptr((*oformat).name);
+            Err(Error::UnsupportedCodec {
+                codec: codec_id,
+                muxer: muxer.to_string_lossy().into_owned(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub(crate) unsafe fn copy_dict(
+    dst: *mut *mut ffi::AVDictionary,
+    src: *const ffi::AVDictionary,
+) -> Result<(), Error> {
+    match ffi::av_dict_copy(dst, src, 0) {
+        e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) unsafe fn copy_chapters<S, D>(
+    input: &Container<S>,
+    output: &mut Container<D>,
+    copy_metadata: bool,
+) -> Result<(), Error> {
+    let ictx = input.as_ptr();
+    let octx = output.as_mut_ptr();
+    let nb_chapters = (*ictx).nb_chapters as usize;
+
+    if nb_chapters == 0 {
+        return Ok(());
+    }
+
+    let chapters = ffi::av_realloc_array(
+        (*octx).chapters as _,
+        (*octx).nb_chapters as usize + nb_chapters,
+        std::mem::size_of::<*mut ffi::AVChapter>(),
+    ) as *mut *mut ffi::AVChapter;
+
+    if chapters.is_null() {
+        return Err(Error::OutOfMemory);
+    }
+    (*octx).chapters = chapters;
+
+    for i in 0..nb_chapters {
+        let src = *(*ictx).chapters.add(i);
+        let dst = ffi::av_mallocz(std::mem::size_of::<ffi::AVChapter>()) as *mut ffi::AVChapter;
+
+        if dst.is_null() {
+            return Err(Error::OutOfMemory);
+        }
+
+        (*dst).id = (*src).id;
+        (*dst).time_base = (*src).time_base;
+        (*dst).start = (*src).start;
+        (*dst).end = (*src).end;
+
+        *chapters.add((*octx).nb_chapters as usize) = dst;
+        (*octx).nb_chapters += 1;
+
+        if copy_metadata {
+            copy_dict(&mut (*dst).metadata, (*src).metadata)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use std::{ffi::CStr, str::FromStr};
+
+use crate::{error::Error, ffi};
+
+/// Format of audio samples. Formats ending in `P` are planar, with one plane per channel.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum SampleFormat {
+    None,
+
+    U8,
+    S16,
+    S32,
+    FLT,
+    DBL,
+
+    U8P,
+    S16P,
+    S32P,
+    FLTP,
+    DBLP,
+
+    S64,
+    S64P,
+}
+
+impl From<ffi::AVSampleFormat> for SampleFormat {
+    #[inline]
+    fn from(format: ffi::AVSampleFormat) -> Self {
+        match format {
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_NONE => SampleFormat::None,
+
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_U8 => SampleFormat::U8,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_S16 => SampleFormat::S16,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_S32 => SampleFormat::S32,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_FLT => SampleFormat::FLT,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_DBL => SampleFormat::DBL,
+
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_U8P => SampleFormat::U8P,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_S16P => SampleFormat::S16P,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_S32P => SampleFormat::S32P,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_FLTP => SampleFormat::FLTP,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_DBLP => SampleFormat::DBLP,
+
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_S64 => SampleFormat::S64,
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_S64P => SampleFormat::S64P,
+
+            ffi::AVSampleFormat::AV_SAMPLE_FMT_NB => SampleFormat::None,
+        }
+    }
+}
+
+impl From<SampleFormat> for ffi::AVSampleFormat {
+    #[inline]
+    fn from(format: SampleFormat) -> ffi::AVSampleFormat {
+        match format {
+            SampleFormat::None => ffi::AVSampleFormat::AV_SAMPLE_FMT_NONE,
+
+            SampleFormat::U8 => ffi::AVSampleFormat::AV_SAMPLE_FMT_U8,
+            SampleFormat::S16 => ffi::AVSampleFormat::AV_SAMPLE_FMT_S16,
+            SampleFormat::S32 => ffi::AVSampleFormat::AV_SAMPLE_FMT_S32,
+            SampleFormat::FLT => ffi::AVSampleFormat::AV_SAMPLE_FMT_FLT,
+            SampleFormat::DBL => ffi::AVSampleFormat::AV_SAMPLE_FMT_DBL,
+
+            SampleFormat::U8P => ffi::AVSampleFormat::AV_SAMPLE_FMT_U8P,
+            SampleFormat::S16P => ffi::AVSampleFormat::AV_SAMPLE_FMT_S16P,
+            SampleFormat::S32P => ffi::AVSampleFormat::AV_SAMPLE_FMT_S32P,
+            SampleFormat::FLTP => ffi::AVSampleFormat::AV_SAMPLE_FMT_FLTP,
+            SampleFormat::DBLP => ffi::AVSampleFormat::AV_SAMPLE_FMT_DBLP,
+
+            SampleFormat::S64 => ffi::AVSampleFormat::AV_SAMPLE_FMT_S64,
+            SampleFormat::S64P => ffi::AVSampleFormat::AV_SAMPLE_FMT_S64P,
+        }
+    }
+}
+
+impl From<libc::c_int> for SampleFormat {
+    /// Converts the `format` field of frames and codec parameters.
+    #[inline]
+    fn from(format: libc::c_int) -> Self {
+        match format {
+            0 => SampleFormat::U8,
+            1 => SampleFormat::S16,
+            2 => SampleFormat::S32,
+            3 => SampleFormat::FLT,
+            4 => SampleFormat::DBL,
+            5 => SampleFormat::U8P,
+            6 => SampleFormat::S16P,
+            7 => SampleFormat::S32P,
+            8 => SampleFormat::FLTP,
+            9 => SampleFormat::DBLP,
+            10 => SampleFormat::S64,
+            11 => SampleFormat::S64P,
+            _ => SampleFormat::None,
+        }
+    }
+}
+
+impl SampleFormat {
+    #[inline]
+    pub fn is_planar(self) -> bool {
+        unsafe { ffi::av_sample_fmt_is_planar(self.into()) == 1 }
+    }
+
+    /// Size of one sample of one channel, or 0 for [`SampleFormat::None`].
+    #[inline]
+    pub fn bytes_per_sample(self) -> usize {
+        unsafe { ffi::av_get_bytes_per_sample(self.into()) as _ }
+    }
+
+    /// The interleaved counterpart of this format, e.g. `FLT` for `FLTP`.
+    #[inline]
+    pub fn packed(self) -> SampleFormat {
+        unsafe { ffi::av_get_packed_sample_fmt(self.into()).into() }
+    }
+
+    /// The planar counterpart of this format, e.g. `FLTP` for `FLT`.
+    #[inline]
+    pub fn planar(self) -> SampleFormat {
+        unsafe { ffi::av_get_planar_sample_fmt(self.into()).into() }
+    }
+
+    /// Short name of the format, as used by FFmpeg, e.g. `fltp`.
+    pub fn name(self) -> Option<&'static str> {
+        unsafe {
+            let ptr = ffi::av_get_sample_fmt_name(self.into());
+            if ptr.is_null() {
+                None
+            } else {
+                CStr::from_ptr(ptr).to_str().ok()
+            }
+        }
+    }
+
+    /// Size of a buffer holding `nb_samples` samples of `nb_channels` channels.
+    ///
+    /// Without `align`, planes are tightly packed; otherwise each plane is padded
+    /// as done for frames allocated by FFmpeg.
+    pub fn buffer_size(
+        self,
+        nb_channels: u32,
+        nb_samples: usize,
+        align: bool,
+    ) -> Result<usize, Error> {
+        let align = if align { 0 } else { 1 };
+
+        unsafe {
+            match ffi::av_samples_get_buffer_size(
+                std::ptr::null_mut(),
+                nb_channels as _,
+                nb_samples as _,
+                self.into(),
+                align,
+            ) {
+                e if e < 0 => Err(Error::from_ffmpeg_error_code(e)),
+                size => Ok(size as _),
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SampleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.name().unwrap_or("none"))
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseSampleError {
+    NulError(std::ffi::NulError),
+    UnknownFormat,
+}
+
+impl std::fmt::Display for ParseSampleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ParseSampleError::NulError(ref e) => e.fmt(f),
+            ParseSampleError::UnknownFormat => write!(f, "unknown sample format"),
+        }
+    }
+}
+
+impl std::error::Error for ParseSampleError {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            ParseSampleError::NulError(ref e) => Some(e),
+            ParseSampleError::UnknownFormat => None,
+        }
+    }
+}
+
+impl From<std::ffi::NulError> for ParseSampleError {
+    fn from(x: std::ffi::NulError) -> ParseSampleError {
+        ParseSampleError::NulError(x)
+    }
+}
+
+impl FromStr for SampleFormat {
+    type Err = ParseSampleError;
+
+    #[inline(always)]
+    fn from_str(s: &str) -> Result<SampleFormat, ParseSampleError> {
+        let cstring = std::ffi::CString::new(s)?;
+        let format = unsafe { ffi::av_get_sample_fmt(cstring.as_ptr()) }.into();
+
+        if format == SampleFormat::None {
+            Err(ParseSampleError::UnknownFormat)
+        } else {
+            Ok(format)
+        }
+    }
+}
+
+/// Rust types that audio samples can be read as, see [`AudioFrame::plane`].
+///
+/// [`AudioFrame::plane`]: crate::frame::AudioFrame::plane
+pub trait Sample: Copy + private::Sealed {
+    /// The interleaved format whose samples are of this type.
+    const FORMAT: SampleFormat;
+}
+
+macro_rules! impl_sample {
+    ($ty:ty, $format:ident) => {
+        impl private::Sealed for $ty {}
+
+        impl Sample for $ty {
+            const FORMAT: SampleFormat = SampleFormat::$format;
+        }
+    };
+}
+
+impl_sample!(u8, U8);
+impl_sample!(i16, S16);
+impl_sample!(i32, S32);
+impl_sample!(f32, FLT);
+impl_sample!(f64, DBL);
+impl_sample!(i64, S64);
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_format() {
+        let format: SampleFormat = "fltp".parse().unwrap();
+        assert_eq!(format, SampleFormat::FLTP);
+        assert!(format.is_planar());
+        assert_eq!(format.packed(), SampleFormat::FLT);
+        assert_eq!(SampleFormat::S16.planar(), SampleFormat::S16P);
+        assert_eq!(format.bytes_per_sample(), 4);
+        assert_eq!(format.to_string(), "fltp");
+        assert_eq!(format.buffer_size(2, 1024, false).unwrap(), 2 * 1024 * 4);
+        assert!("foo".parse::<SampleFormat>().is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{codec::CodecParameters, container::Container, ffi, MediaType, Rational};
+use super::IndexEntryIter;
+use crate::{codec::CodecParameters, container::Container, error::Error, ffi, MediaType, Rational};
 
 pub struct Stream<'a, D> {
     container: &'a Container<D>,
@@ -16,6 +17,12 @@ impl<'a, D> Stream<'a, D> {
         *(*self.container.as_ptr()).streams.add(self.index as usize)
     }
 
+    /// Parameters of the stream, shared with the container.
+    ///
+    /// [`CodecParameters::make_mut`] copies them rather than changing the stream,
+    /// see [`StreamMut::parameters_mut`] for output streams.
+    ///
+    /// [`StreamMut::parameters_mut`]: super::StreamMut::parameters_mut
     pub fn parameters(&self) -> CodecParameters<D> {
         unsafe {
             let ptr = (*self.as_ptr()).codecpar;
@@ -51,6 +58,37 @@ impl<'a, D> Stream<'a, D> {
     pub fn avg_frame_rate(&self) -> Rational {
         unsafe { Rational::from((*self.as_ptr()).avg_frame_rate) }
     }
+
+    /// Checks whether this stream matches an FFmpeg stream specifier, e.g. `v:0` or `a`.
+    pub fn matches_specifier(&self, spec: &str) -> Result<bool, Error> {
+        let cspec =
+            std::ffi::CString::new(spec).map_err(|_| Error::InvalidStreamSpecifier(spec.into()))?;
+
+        unsafe {
+            match ffi::avformat_match_stream_specifier(
+                self.container.as_ptr() as _,
+                self.as_ptr() as _,
+                cspec.as_ptr(),
+            ) {
+                e if e < 0 => Err(Error::InvalidStreamSpecifier(spec.into())),
+                0 => Ok(false),
+                _ => Ok(true),
+            }
+        }
+    }
+
+    /// Number of entries in the demuxer index of this stream.
+    pub fn nb_index_entries(&self) -> usize {
+        unsafe { ffi::avformat_index_get_entries_count(self.as_ptr()) as _ }
+    }
+
+    /// Iterates over the demuxer index of this stream.
+    ///
+    /// The index may be empty or partial, depending on the container format and on how
+    /// much of the input has been read so far.
+    pub fn index_entries(&self) -> IndexEntryIter {
+        unsafe { IndexEntryIter::new(self.as_ptr() as _) }
+    }
 }
 
 impl<'a, D> PartialEq for Stream<'a, D> {
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use crate::ffi;
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct IndexFlags: i32 {
+        const KEYFRAME = ffi::AVINDEX_KEYFRAME;
+        const DISCARD_FRAME = ffi::AVINDEX_DISCARD_FRAME;
+    }
+}
+
+/// A seek point of a stream, either read from the demuxer index or found by scanning packets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IndexEntry {
+    pos: i64,
+    timestamp: i64,
+    flags: IndexFlags,
+    size: i32,
+    min_distance: i32,
+}
+
+impl IndexEntry {
+    #[inline]
+    pub(crate) fn new(pos: i64, timestamp: i64, flags: IndexFlags, size: i32) -> Self {
+        IndexEntry {
+            pos,
+            timestamp,
+            flags,
+            size,
+            min_distance: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::AVIndexEntry) -> Self {
+        let entry = &*ptr;
+        IndexEntry {
+            pos: entry.pos,
+            timestamp: entry.timestamp,
+            flags: IndexFlags::from_bits_truncate(entry.flags()),
+            size: entry.size(),
+            min_distance: entry.min_distance,
+        }
+    }
+
+    /// Byte position of the entry in the file, or `-1` if unknown.
+    #[inline]
+    pub fn pos(&self) -> i64 {
+        self.pos
+    }
+
+    /// Timestamp of the entry, in the time base of its stream.
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    #[inline]
+    pub fn flags(&self) -> IndexFlags {
+        self.flags
+    }
+
+    #[inline]
+    pub fn is_key(&self) -> bool {
+        self.flags.contains(IndexFlags::KEYFRAME)
+    }
+
+    #[inline]
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    /// Minimum distance to the previous keyframe, used to avoid unneeded searching.
+    #[inline]
+    pub fn min_distance(&self) -> i32 {
+        self.min_distance
+    }
+}
+
+pub struct IndexEntryIter<'a> {
+    ptr: *mut ffi::AVStream,
+    current: i32,
+    count: i32,
+    _marker: PhantomData<&'a ffi::AVStream>,
+}
+
+impl<'a> IndexEntryIter<'a> {
+    pub(crate) unsafe fn new(ptr: *mut ffi::AVStream) -> Self {
+        IndexEntryIter {
+            ptr,
+            current: 0,
+            count: ffi::avformat_index_get_entries_count(ptr),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for IndexEntryIter<'a> {
+    type Item = IndexEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current < self.count {
+            let ptr = unsafe { ffi::avformat_index_get_entry(self.ptr, self.current) };
+            self.current += 1;
+
+            if !ptr.is_null() {
+                return Some(unsafe { IndexEntry::from_ptr(ptr) });
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count - self.current) as usize;
+        (0, Some(remaining))
+    }
+}
@@ -1,5 +1,7 @@
 mod imp;
+mod index;
 mod stream_mut;
 
 pub use imp::{Stream, StreamIter};
+pub use index::{IndexEntry, IndexEntryIter, IndexFlags};
 pub use stream_mut::{StreamIterMut, StreamMut};
@@ -1,7 +1,11 @@
 use std::ops::Deref;
 
 use super::Stream;
-use crate::{codec::CodecParameters, container::Container, ffi, MediaType, Rational};
+use crate::{
+    codec::{CodecParameters, CodecParametersMut},
+    container::Container,
+    ffi, MediaType, Rational,
+};
 
 pub struct StreamMut<'a, D> {
     container: &'a mut Container<D>,
@@ -27,6 +31,11 @@ impl<'a, D> StreamMut<'a, D> {
         }
     }
 
+    /// Mutable access to the parameters of the stream, e.g. to clear the codec tag.
+    pub fn parameters_mut(&mut self) -> CodecParametersMut<'_> {
+        unsafe { CodecParametersMut::wrap((*self.as_mut_ptr()).codecpar) }
+    }
+
     pub fn set_time_base<R: Into<Rational>>(&mut self, time_base: R) {
         unsafe {
             (*self.as_mut_ptr()).time_base = time_base.into().into();
@@ -0,0 +1,424 @@
+use std::{
+    ffi::{CStr, CString},
+    marker::PhantomData,
+};
+
+use crate::{
+    codec::{
+        CodecId, CodecParameters, CodecProperties, Decoder, Encoder, OpenedDecoder, OpenedEncoder,
+    },
+    error::Error,
+    ffi,
+    packet::Packet,
+    stream::Stream,
+    Rational,
+};
+
+/// ASS header given to text subtitle encoders when the source has none,
+/// e.g. when creating subtitles from [`Subtitle::from_text`].
+pub const DEFAULT_ASS_HEADER: &str = "[Script Info]\r
+; Script generated by rsav\r
+ScriptType: v4.00+\r
+PlayResX: 384\r
+PlayResY: 288\r
+ScaledBorderAndShadow: yes\r
+YCbCr Matrix: None\r
+\r
+[V4+ Styles]\r
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, \
+Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, \
+Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\r
+Style: Default,Arial,16,&Hffffff,&Hffffff,&H0,&H0,0,0,0,0,100,100,0,0,1,1,0,2,10,10,10,1\r
+\r
+[Events]\r
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\r
+";
+
+/// A subtitle event, displayed from [`Subtitle::start`] to [`Subtitle::end`].
+pub struct Subtitle(ffi::AVSubtitle);
+
+unsafe impl Send for Subtitle {}
+unsafe impl Sync for Subtitle {}
+
+impl Subtitle {
+    #[inline]
+    pub(crate) unsafe fn as_ptr(&self) -> *const ffi::AVSubtitle {
+        &self.0
+    }
+
+    #[inline]
+    pub(crate) unsafe fn as_mut_ptr(&mut self) -> *mut ffi::AVSubtitle {
+        &mut self.0
+    }
+}
+
+impl Subtitle {
+    /// A subtitle without timestamps nor rects.
+    pub fn empty() -> Self {
+        unsafe {
+            let mut sub: ffi::AVSubtitle = std::mem::zeroed();
+            sub.pts = ffi::AV_NOPTS_VALUE;
+            Subtitle(sub)
+        }
+    }
+
+    /// A text subtitle displayed from `start` to `end`, in `AV_TIME_BASE` units.
+    pub fn from_text(start: i64, end: i64, text: &str) -> Result<Self, Error> {
+        let duration = u32::try_from((end - start) / 1000).map_err(|_| Error::InvalidRange)?;
+
+        let mut subtitle = Self::empty();
+        subtitle.set_pts(Some(start));
+        subtitle.set_end_display_time(duration);
+        subtitle.add_text(text)?;
+        Ok(subtitle)
+    }
+
+    /// Presentation timestamp, in `AV_TIME_BASE` units.
+    #[inline]
+    pub fn pts(&self) -> Option<i64> {
+        match self.0.pts {
+            ffi::AV_NOPTS_VALUE => None,
+            pts => Some(pts),
+        }
+    }
+
+    #[inline]
+    pub fn set_pts(&mut self, pts: Option<i64>) {
+        self.0.pts = pts.unwrap_or(ffi::AV_NOPTS_VALUE);
+    }
+
+    /// Start of the display, in milliseconds relative to [`Subtitle::pts`].
+    #[inline]
+    pub fn start_display_time(&self) -> u32 {
+        self.0.start_display_time
+    }
+
+    /// End of the display, in milliseconds relative to [`Subtitle::pts`].
+    #[inline]
+    pub fn end_display_time(&self) -> u32 {
+        self.0.end_display_time
+    }
+
+    #[inline]
+    pub fn set_start_display_time(&mut self, start_display_time: u32) {
+        self.0.start_display_time = start_display_time;
+    }
+
+    #[inline]
+    pub fn set_end_display_time(&mut self, end_display_time: u32) {
+        self.0.end_display_time = end_display_time;
+    }
+
+    /// Start of the display, in `AV_TIME_BASE` units.
+    #[inline]
+    pub fn start(&self) -> Option<i64> {
+        self.pts()
+            .map(|pts| pts + self.start_display_time() as i64 * 1000)
+    }
+
+    /// End of the display, in `AV_TIME_BASE` units.
+    #[inline]
+    pub fn end(&self) -> Option<i64> {
+        self.pts()
+            .map(|pts| pts + self.end_display_time() as i64 * 1000)
+    }
+
+    #[inline]
+    pub fn nb_rects(&self) -> usize {
+        self.0.num_rects as _
+    }
+
+    pub fn rects(&self) -> RectIter {
+        RectIter {
+            ptr: self.0.rects,
+            index: 0,
+            len: self.nb_rects(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Adds a plain text rect, escaped into an ASS event with the `Default` style.
+    pub fn add_text(&mut self, text: &str) -> Result<(), Error> {
+        let mut event = format!("{},0,Default,,0,0,0,,", self.nb_rects());
+
+        for c in text.chars() {
+            match c {
+                '\n' => event.push_str("\\N"),
+                '\r' => {}
+                '\\' | '{' | '}' => {
+                    event.push('\\');
+                    event.push(c);
+                }
+                c => event.push(c),
+            }
+        }
+
+        self.add_ass(&event)
+    }
+
+    /// Adds an ASS rect, in the format output by decoders:
+    /// `ReadOrder,Layer,Style,Name,MarginL,MarginR,MarginV,Effect,Text`.
+    pub fn add_ass(&mut self, event: &str) -> Result<(), Error> {
+        let event = CString::new(event).map_err(|_| Error::InvalidArgument)?;
+        let len = self.nb_rects();
+
+        unsafe {
+            let rects = ffi::av_realloc_array(
+                self.0.rects as *mut _,
+                len + 1,
+                std::mem::size_of::<*mut ffi::AVSubtitleRect>(),
+            ) as *mut *mut ffi::AVSubtitleRect;
+            if rects.is_null() {
+                return Err(Error::OutOfMemory);
+            }
+            self.0.rects = rects;
+
+            let rect = ffi::av_mallocz(std::mem::size_of::<ffi::AVSubtitleRect>())
+                as *mut ffi::AVSubtitleRect;
+            if rect.is_null() {
+                return Err(Error::OutOfMemory);
+            }
+            // Owned by the subtitle from now on, so that it is freed on error.
+            *rects.add(len) = rect;
+            self.0.num_rects += 1;
+
+            (*rect).ass = ffi::av_strdup(event.as_ptr());
+            if (*rect).ass.is_null() {
+                return Err(Error::OutOfMemory);
+            }
+            (*rect).type_ = ffi::AVSubtitleType::SUBTITLE_ASS;
+        }
+
+        // Text subtitle.
+        self.0.format = 1;
+        Ok(())
+    }
+}
+
+impl Default for Subtitle {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Drop for Subtitle {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::avsubtitle_free(&mut self.0);
+        }
+    }
+}
+
+impl std::fmt::Debug for Subtitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Subtitle")
+            .field("pts", &self.pts())
+            .field("start_display_time", &self.start_display_time())
+            .field("end_display_time", &self.end_display_time())
+            .field("rects", &self.rects().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A region of a subtitle.
+#[derive(Debug)]
+pub enum Rect<'a> {
+    Bitmap(Bitmap<'a>),
+    /// Plain text.
+    Text(&'a str),
+    /// An ASS `Dialogue` event line, as output by all text subtitle decoders.
+    Ass(&'a str),
+}
+
+impl<'a> Rect<'a> {
+    unsafe fn from_ptr(ptr: &'a ffi::AVSubtitleRect) -> Option<Self> {
+        match ptr.type_ {
+            ffi::AVSubtitleType::SUBTITLE_BITMAP => Some(Rect::Bitmap(Bitmap(ptr))),
+            ffi::AVSubtitleType::SUBTITLE_TEXT => Some(Rect::Text(cstr(ptr.text))),
+            ffi::AVSubtitleType::SUBTITLE_ASS => Some(Rect::Ass(cstr(ptr.ass))),
+            _ => None,
+        }
+    }
+}
+
+unsafe fn cstr<'a>(ptr: *const libc::c_char) -> &'a str {
+    if ptr.is_null() {
+        ""
+    } else {
+        CStr::from_ptr(ptr).to_str().unwrap_or_default()
+    }
+}
+
+/// A paletted image, e.g. from DVD or DVB subtitles.
+#[derive(Clone, Copy)]
+pub struct Bitmap<'a>(&'a ffi::AVSubtitleRect);
+
+impl<'a> Bitmap<'a> {
+    #[inline]
+    pub fn x(&self) -> i32 {
+        self.0.x
+    }
+
+    #[inline]
+    pub fn y(&self) -> i32 {
+        self.0.y
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.0.w as _
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.0.h as _
+    }
+
+    #[inline]
+    pub fn nb_colors(&self) -> usize {
+        self.0.nb_colors as _
+    }
+
+    /// Colors of the palette, as native-endian ARGB.
+    pub fn palette(&self) -> &'a [u32] {
+        let ptr = self.0.data[1];
+        if ptr.is_null() {
+            return &[];
+        }
+
+        unsafe { std::slice::from_raw_parts(ptr as *const u32, self.nb_colors()) }
+    }
+
+    /// Number of bytes between the starts of two rows of [`Bitmap::indices`].
+    #[inline]
+    pub fn linesize(&self) -> usize {
+        self.0.linesize[0] as _
+    }
+
+    /// Palette index of every pixel, row by row.
+    pub fn indices(&self) -> &'a [u8] {
+        let ptr = self.0.data[0];
+        if ptr.is_null() {
+            return &[];
+        }
+
+        unsafe { std::slice::from_raw_parts(ptr, self.linesize() * self.height() as usize) }
+    }
+}
+
+impl std::fmt::Debug for Bitmap<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Bitmap")
+            .field("x", &self.x())
+            .field("y", &self.y())
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("nb_colors", &self.nb_colors())
+            .finish()
+    }
+}
+
+pub struct RectIter<'a> {
+    ptr: *mut *mut ffi::AVSubtitleRect,
+    index: usize,
+    len: usize,
+    _marker: PhantomData<&'a Subtitle>,
+}
+
+impl<'a> Iterator for RectIter<'a> {
+    type Item = Rect<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.len {
+            let rect = unsafe { (*self.ptr.add(self.index)).as_ref() };
+            self.index += 1;
+
+            if let Some(rect) = rect.and_then(|rect| unsafe { Rect::from_ptr(rect) }) {
+                return Some(rect);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len - self.index))
+    }
+}
+
+/// Converts text subtitles from one format to another, e.g. SubRip or WebVTT
+/// to mov_text when remuxing to MP4.
+pub struct SubtitleConverter {
+    decoder: OpenedDecoder,
+    encoder: OpenedEncoder,
+}
+
+impl SubtitleConverter {
+    /// Creates a converter for the packets of `stream`, keeping its time base.
+    pub fn new<D>(stream: &Stream<D>, codec: CodecId) -> Result<Self, Error> {
+        for id in [stream.parameters().codec_id(), codec] {
+            if !is_text_subtitle(id) {
+                return Err(Error::NotTextSubtitle(id));
+            }
+        }
+
+        let decoder = Decoder::from_stream(stream)?.open()?;
+
+        let mut encoder = Encoder::from_id(codec)?;
+        encoder.set_time_base(stream.time_base());
+        encoder.set_subtitle_header(
+            decoder
+                .0
+                .subtitle_header()
+                .unwrap_or(DEFAULT_ASS_HEADER.as_bytes()),
+        )?;
+        let encoder = encoder.open()?;
+
+        Ok(SubtitleConverter { decoder, encoder })
+    }
+
+    /// Parameters of the converted stream, to be set on an output stream.
+    pub fn parameters<D>(&self) -> Result<CodecParameters<D>, Error> {
+        self.encoder.parameters()
+    }
+
+    /// Time base of the converted packets.
+    pub fn time_base(&self) -> Rational {
+        self.encoder.time_base()
+    }
+
+    /// Converts a packet of the input stream, returning `None` if it did not complete a subtitle.
+    pub fn convert(&mut self, packet: &Packet) -> Result<Option<Packet>, Error> {
+        match self.decoder.decode_subtitle(packet)? {
+            Some(subtitle) => {
+                let mut converted = self.encoder.encode_subtitle(&subtitle)?;
+                converted.set_stream_index(packet.stream_index());
+                Ok(Some(converted))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn is_text_subtitle(id: CodecId) -> bool {
+    id.descriptor()
+        .is_some_and(|desc| desc.props().contains(CodecProperties::TEXT_SUB))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_text() {
+        let subtitle = Subtitle::from_text(1_000_000, 3_500_000, "Hello\n{world}").unwrap();
+        assert_eq!(subtitle.start(), Some(1_000_000));
+        assert_eq!(subtitle.end(), Some(3_500_000));
+
+        let rects: Vec<_> = subtitle.rects().collect();
+        assert!(matches!(
+            rects[..],
+            [Rect::Ass("0,0,Default,,0,0,0,,Hello\\N\\{world\\}")]
+        ));
+    }
+}